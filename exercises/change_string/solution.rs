@@ -0,0 +1,3 @@
+pub fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
@@ -0,0 +1,12 @@
+// Exercise: change a String through a mutable reference
+//
+// Like change() in the references lesson, append ", world" to the String that `some_string` refers to.
+// The caller keeps ownership and sees the change afterwards.
+//
+//     let mut s = String::from("hello");
+//     change(&mut s);
+//     // s == "hello, world"
+
+pub fn change(some_string: &mut String) {
+    todo!("append \", world\" to {some_string:?}")
+}
@@ -0,0 +1,21 @@
+#[test]
+fn appends_world() {
+    let mut s = String::from("hello");
+    change(&mut s);
+    assert_eq!(s, "hello, world");
+}
+
+#[test]
+fn appends_every_time() {
+    let mut s = String::from("hi");
+    change(&mut s);
+    change(&mut s);
+    assert_eq!(s, "hi, world, world");
+}
+
+#[test]
+fn works_on_an_empty_string() {
+    let mut s = String::new();
+    change(&mut s);
+    assert_eq!(s, ", world");
+}
@@ -0,0 +1,5 @@
+pub fn first_word(s: &str) -> &str {
+    let s = s.trim_start();
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    &s[..end]
+}
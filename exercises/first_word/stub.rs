@@ -0,0 +1,13 @@
+// Exercise: first_word for &str
+//
+// Return the first word of `s`: the slice before the first whitespace.
+// Whitespace at the start is skipped, and a text without words gives "".
+//
+//     first_word("hello world") == "hello"
+//     first_word("  \tRust")    == "Rust"
+//
+// Return a slice of `s` itself, do not build a new String.
+
+pub fn first_word(s: &str) -> &str {
+    todo!("return the first word of {s:?}")
+}
@@ -0,0 +1,38 @@
+#[test]
+fn first_of_several_words() {
+    assert_eq!(first_word("hello this word"), "hello");
+}
+
+#[test]
+fn a_single_word() {
+    assert_eq!(first_word("hello"), "hello");
+}
+
+#[test]
+fn leading_whitespace_is_skipped() {
+    assert_eq!(first_word("  \tRust slices"), "Rust");
+}
+
+#[test]
+fn tabs_and_newlines_end_a_word() {
+    assert_eq!(first_word("one\ttwo"), "one");
+    assert_eq!(first_word("one\ntwo"), "one");
+}
+
+#[test]
+fn no_words() {
+    assert_eq!(first_word(""), "");
+    assert_eq!(first_word("   "), "");
+}
+
+#[test]
+fn works_on_a_string() {
+    let s = String::from("hello world");
+    assert_eq!(first_word(&s), "hello");
+}
+
+#[test]
+fn the_word_is_a_slice_of_the_text() {
+    let s = String::from("hello world");
+    assert_eq!(first_word(&s).as_ptr(), s.as_ptr());
+}
@@ -0,0 +1,4 @@
+pub fn return_ownership_tup(some_string: String) -> (usize, String) {
+    let len = some_string.len();
+    (len, some_string)
+}
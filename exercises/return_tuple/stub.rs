@@ -0,0 +1,13 @@
+// Exercise: return ownership with a tuple
+//
+// Like return_ownership_tup() in the ownership lesson, take ownership of a String
+// and give it back together with its length in bytes.
+//
+//     let (len, s) = return_ownership_tup(String::from("hello"));
+//     // len == 5, s == "hello"
+//
+// Give back the same String: moving it is enough, cloning it is not needed.
+
+pub fn return_ownership_tup(some_string: String) -> (usize, String) {
+    todo!("return the length and {some_string:?}")
+}
@@ -0,0 +1,24 @@
+#[test]
+fn length_and_string() {
+    let (len, s) = return_ownership_tup(String::from("hello"));
+    assert_eq!(len, 5);
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn empty_string() {
+    assert_eq!(return_ownership_tup(String::new()), (0, String::new()));
+}
+
+#[test]
+fn length_counts_bytes() {
+    assert_eq!(return_ownership_tup(String::from("héllo")).0, 6);
+}
+
+#[test]
+fn the_same_string_comes_back() {
+    let s = String::from("hello");
+    let ptr = s.as_ptr();
+    let (_, s) = return_ownership_tup(s);
+    assert_eq!(s.as_ptr(), ptr, "the String was copied instead of moved back");
+}
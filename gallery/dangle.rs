@@ -0,0 +1,7 @@
+// From the dangling references note: the String is dropped when dangle() returns,
+// so a reference to it would point to freed memory
+fn dangle() -> &String {
+    let s = String::from("hello");
+
+    &s
+}
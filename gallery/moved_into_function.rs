@@ -0,0 +1,10 @@
+// From intro_to_string(): passing a String to a function moves it, like an assignment does
+fn takes_ownership(some_string: String) {
+    println!("{}", some_string);
+}
+
+fn intro_to_string() {
+    let s3 = String::from("hyo");
+    takes_ownership(s3);
+    println!("s3: {}", s3); // Value used after being moved
+}
@@ -0,0 +1,10 @@
+// From basic_ownership(): a variable only lives until the end of the block it was declared in
+fn basic_ownership() {
+    let s = "hello";
+    {
+        let s1 = "hello";
+        // Scope lies only in this block
+    }
+    println!("{:?}", s1); // Cannot find value `s1` in this scope
+    println!("{:?}", s);
+}
@@ -0,0 +1,9 @@
+// From mutable_reference(): only one mutable reference to a value at a time
+fn mutable_reference() {
+    let mut s = String::from("hello");
+
+    let r1 = &mut s;
+    let r2 = &mut s; // cannot borrow `s` as mutable more than once at a time
+
+    println!("{r1}, {r2}");
+}
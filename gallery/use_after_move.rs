@@ -0,0 +1,6 @@
+// From intro_to_string(): `let s2 = s1;` moves the String, s1 can no longer be used
+fn intro_to_string() {
+    let s1 = String::from("hello");
+    let s2 = s1; // Value moved here
+    println!("s1: {}, s2: {}", s1, s2); // Value used after being moved
+}
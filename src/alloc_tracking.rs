@@ -0,0 +1,196 @@
+// Allocation tracking
+/*
+The ownership lessons claim that String::from allocates, that clone() deep-copies and that values are
+freed at the closing brace. With the `alloc-tracking` cargo feature enabled,
+
+    cargo run --features alloc-tracking -- run ownership::intro_to_ownership
+
+a counting allocator wraps the system allocator and the lessons print the allocations and frees
+of each step next to the claim it proves:
+
+    let s = String::from("hello");      [1 alloc, 0 frees]
+
+Counts are kept per thread, so lessons running side by side in tests do not see each other's allocations,
+and writing to the Console is not counted, since that is the handbook talking rather than the lesson.
+Without the feature the system allocator is used directly and nothing extra is printed.
+*/
+
+#[cfg(feature = "alloc-tracking")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::{self, Write};
+
+use crate::console::Console;
+
+#[cfg(feature = "alloc-tracking")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counts {
+    pub allocs: usize,
+    pub frees: usize,
+    pub reallocs: usize,
+    pub bytes: usize,
+}
+
+thread_local! {
+    // Const initialized and without a destructor, so the allocator can use them without allocating
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts { allocs: 0, frees: 0, reallocs: 0, bytes: 0 })
+    };
+    static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+#[cfg(feature = "alloc-tracking")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-tracking")]
+fn record(update: impl FnOnce(&mut Counts)) {
+    // try_with: the allocator is still called while a thread's locals are being torn down
+    let _ = PAUSED.try_with(|paused| {
+        if !paused.get() {
+            let _ = COUNTS.try_with(|counts| {
+                let mut current = counts.get();
+                update(&mut current);
+                counts.set(current);
+            });
+        }
+    });
+}
+
+#[cfg(feature = "alloc-tracking")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(|counts| {
+            counts.allocs += 1;
+            counts.bytes += layout.size();
+        });
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(|counts| counts.frees += 1);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(|counts| {
+            counts.reallocs += 1;
+            counts.bytes += new_size.saturating_sub(layout.size());
+        });
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-tracking")
+}
+
+// Counts of the current thread so far, all zero when the feature is off
+pub fn counts() -> Counts {
+    COUNTS.with(Cell::get)
+}
+
+// Runs `f` without counting its allocations
+pub fn untracked<T>(f: impl FnOnce() -> T) -> T {
+    let was_paused = PAUSED.with(|paused| paused.replace(true));
+    let result = f();
+    PAUSED.with(|paused| paused.set(was_paused));
+    result
+}
+
+// Marks the start of a lesson step, report() then prints what happened since
+pub struct Step {
+    start: Counts,
+}
+
+impl Step {
+    pub fn start() -> Step {
+        Step { start: counts() }
+    }
+
+    pub fn counts(&self) -> Counts {
+        let now = counts();
+        Counts {
+            allocs: now.allocs - self.start.allocs,
+            frees: now.frees - self.start.frees,
+            reallocs: now.reallocs - self.start.reallocs,
+            bytes: now.bytes - self.start.bytes,
+        }
+    }
+
+    // Prints `code` with the allocations of the step, does nothing without the feature
+    pub fn report(&self, out: &mut Console, code: &str) -> io::Result<()> {
+        if !enabled() {
+            return Ok(());
+        }
+        let counts = self.counts();
+        let mut line = format!(
+            "    {code:<38} [{} alloc{}, {} free{}",
+            counts.allocs,
+            if counts.allocs == 1 { "" } else { "s" },
+            counts.frees,
+            if counts.frees == 1 { "" } else { "s" },
+        );
+        if counts.reallocs > 0 {
+            line += &format!(", {} realloc", counts.reallocs);
+        }
+        if counts.bytes > 0 {
+            line += &format!(", {} bytes requested", counts.bytes);
+        }
+        writeln!(out, "{line}]")
+    }
+}
+
+// Whether `line` is one printed by Step::report()
+pub fn is_report(line: &str) -> bool {
+    let Some(code) = line.strip_prefix("    ") else {
+        return false;
+    };
+    let Some((_, counts)) = code.rsplit_once(" [") else {
+        return false;
+    };
+    let Some(counts) = counts.strip_suffix(']') else {
+        return false;
+    };
+    let mut parts = counts.split(", ");
+    let counted = |part: Option<&str>, unit: &str| {
+        part.and_then(|part| part.split_once(' '))
+            .is_some_and(|(count, rest)| {
+                count.parse::<usize>().is_ok() && rest.trim_end_matches('s') == unit
+            })
+    };
+    counted(parts.next(), "alloc") && counted(parts.next(), "free")
+}
+
+#[cfg(all(test, feature = "alloc-tracking"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_from_allocates_and_drop_frees() {
+        let step = Step::start();
+        let s = String::from("hello");
+        assert_eq!(step.counts().allocs, 1);
+        assert_eq!(step.counts().frees, 0);
+
+        let moved = s;
+        assert_eq!(step.counts().allocs, 1);
+
+        let copy = moved.clone();
+        assert_eq!(step.counts().allocs, 2);
+        drop(copy);
+        drop(moved);
+        assert_eq!(step.counts().frees, 2);
+    }
+
+    #[test]
+    fn untracked_allocations_are_not_counted() {
+        let step = Step::start();
+        let s = untracked(|| String::from("not counted"));
+        assert_eq!(step.counts(), Counts::default());
+        untracked(|| drop(s));
+        assert_eq!(step.counts(), Counts::default());
+    }
+}
@@ -0,0 +1,170 @@
+// Calculator
+/*
+A small interactive program that puts the integer operators from numeric_operations() to work:
+    +  addition        -  subtraction     *  multiplication
+    /  division        %  remainder
+
+It reads two i32 numbers and an operator, the same way the first input examples did:
+    io::stdin().read_line(&mut input)
+    input.trim().parse::<i32>()
+
+But instead of silently returning when parse() fails it shows the text that could not be parsed
+and asks again. Integers also have edges, i32 only holds -2147483648 to 2147483647:
+    >. In a debug build `i32::MAX + 1` panics with "attempt to add with overflow".
+    >. In a release build the same expression wraps around to i32::MIN.
+    >. Dividing by zero panics in both builds, and so does `i32::MIN / -1`, the one division that overflows.
+So the calculator uses the checked_* methods, which return None instead of panicking, and explains what happened.
+*/
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+
+pub fn calculator(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "-----------Calculator-------------")?;
+    writeln!(out, "Type `quit` at any prompt to leave the calculator.")?;
+    while let Some((a, op, b)) = read_calculation(out)? {
+        match calculate(a, op, b) {
+            Ok(result) => writeln!(out, "{a} {op} {b} = {result}")?,
+            Err(CalcError::DivisionByZero) => {
+                let message = if op == '/' {
+                    "attempt to divide by zero"
+                } else {
+                    "attempt to calculate the remainder with a divisor of zero"
+                };
+                writeln!(out, "{a} {op} {b}: cannot divide by zero.")?;
+                writeln!(
+                    out,
+                    "Integer division by zero has no answer, Rust panics with \"{message}\"."
+                )?;
+            }
+            Err(CalcError::Overflow { wrapped }) => {
+                writeln!(
+                    out,
+                    "{a} {op} {b}: the result does not fit in an i32 ({} to {}).",
+                    i32::MIN,
+                    i32::MAX
+                )?;
+                match wrapped {
+                    Some(wrapped) => writeln!(
+                        out,
+                        "A debug build panics here, a release build silently wraps around to {wrapped}."
+                    )?,
+                    None => {
+                        let verb = if op == '/' {
+                            "divide"
+                        } else {
+                            "calculate the remainder"
+                        };
+                        writeln!(
+                            out,
+                            "Debug and release builds both panic here: \"attempt to {verb} with overflow\"."
+                        )?;
+                    }
+                }
+            }
+            Err(CalcError::UnknownOperator(op)) => {
+                writeln!(out, "`{op}` is not one of +, -, *, /, %.")?;
+            }
+        }
+    }
+    writeln!(out, "Leaving the calculator.")?;
+    Ok(())
+}
+
+pub enum CalcError {
+    DivisionByZero,
+    // The value a release build would have produced by wrapping around,
+    // None for / and %, which panic on overflow in release builds too
+    Overflow { wrapped: Option<i32> },
+    UnknownOperator(char),
+}
+
+pub fn calculate(a: i32, op: char, b: i32) -> Result<i32, CalcError> {
+    if (op == '/' || op == '%') && b == 0 {
+        return Err(CalcError::DivisionByZero);
+    }
+    let (checked, wrapped) = match op {
+        '+' => (a.checked_add(b), Some(a.wrapping_add(b))),
+        '-' => (a.checked_sub(b), Some(a.wrapping_sub(b))),
+        '*' => (a.checked_mul(b), Some(a.wrapping_mul(b))),
+        // i32::MIN / -1 is the one division that overflows: 2147483648 is one too many for an i32
+        '/' => (a.checked_div(b), None),
+        '%' => (a.checked_rem(b), None),
+        _ => return Err(CalcError::UnknownOperator(op)),
+    };
+    checked.ok_or(CalcError::Overflow { wrapped })
+}
+
+// Number, operator, number, or None as soon as the learner quits or the input ends
+fn read_calculation(out: &mut Console) -> io::Result<Option<(i32, char, i32)>> {
+    let Some(a) = read_number(out, "Please input your Number 1")? else {
+        return Ok(None);
+    };
+    let Some(op) = read_operator(out)? else {
+        return Ok(None);
+    };
+    let Some(b) = read_number(out, "Please input your Number 2")? else {
+        return Ok(None);
+    };
+    Ok(Some((a, op, b)))
+}
+
+// Asks until the answer parses as an i32, None when the learner quits or the input ends
+fn read_number(out: &mut Console, prompt: &str) -> io::Result<Option<i32>> {
+    loop {
+        writeln!(out, "{prompt}")?;
+        let Some(input) = out.read_line()? else {
+            return Ok(None);
+        };
+        if input.trim() == "quit" {
+            return Ok(None);
+        }
+        match input.trim().parse::<i32>() {
+            Ok(n) => return Ok(Some(n)),
+            Err(err) => writeln!(out, "`{}` is not an i32: {err}. Try again.", input.trim())?,
+        }
+    }
+}
+
+fn read_operator(out: &mut Console) -> io::Result<Option<char>> {
+    loop {
+        writeln!(out, "Please input an operator (+, -, *, /, %)")?;
+        let Some(input) = out.read_line()? else {
+            return Ok(None);
+        };
+        match input.trim() {
+            "quit" => return Ok(None),
+            op @ ("+" | "-" | "*" | "/" | "%") => return Ok(op.chars().next()),
+            other => writeln!(out, "`{other}` is not one of +, -, *, /, %. Try again.")?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_returned_instead_of_panicking() {
+        assert!(matches!(calculate(7, '%', 2), Ok(1)));
+        assert!(matches!(
+            calculate(1, '/', 0),
+            Err(CalcError::DivisionByZero)
+        ));
+        assert!(matches!(
+            calculate(i32::MIN, '/', -1),
+            Err(CalcError::Overflow { wrapped: None })
+        ));
+        assert!(matches!(
+            calculate(i32::MAX, '+', 1),
+            Err(CalcError::Overflow {
+                wrapped: Some(i32::MIN)
+            })
+        ));
+        assert!(matches!(
+            calculate(2, '^', 3),
+            Err(CalcError::UnknownOperator('^'))
+        ));
+    }
+}
@@ -4,30 +4,55 @@
 
 // 1. if Expressions
 
-use std::io;
+pub mod trace;
 
-pub fn control_flow() {
-    println!("-----------Control Flow-------------");
-    // let mut input: String = String::new();
-    // println!("Please input your Number::");
-    // io::stdin().read_line(&mut input).expect("Failed to read line");
-    // control_flow_example(input.trim().parse::<i32>().unwrap());
-    //
-    // // Using if in a let Statement
-    // let x = if input.trim().parse::<i32>().unwrap() == 5 { 5 } else { 0 };
-    // println!("The value of x is {}", x);
-    println!("------------Loops------------------");
-    loops();
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::quiz::{Answer, Question};
+
+pub fn control_flow(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "-----------Control Flow-------------")?;
+    writeln!(out, "Please input your Number:: (or quit)")?;
+    while let Some(input) = out.read_line()? {
+        let input = input.trim();
+        if input == "quit" {
+            break;
+        }
+        // parse() returns a Result, so a typo is reported instead of panicking like unwrap() would
+        let x = match input.parse::<i32>() {
+            Ok(x) => x,
+            Err(err) => {
+                writeln!(out, "`{}` is not a whole number ({}), try again", input, err)?;
+                continue;
+            }
+        };
+        control_flow_example(out, x)?;
+
+        // Using if in a let Statement
+        // Both arms must have the same type, because y can only have one type
+        let y = if x == 5 { 5 } else { 0 };
+        writeln!(out, "The value of y is {}", y)?;
+        let sign = if x > 0 { "positive" } else if x < 0 { "negative" } else { "zero" };
+        writeln!(out, "let sign = if ... gives {}", sign)?;
+        writeln!(out, "Please input your Number:: (or quit)")?;
+    }
+    Ok(())
 }
 
-fn control_flow_example(x: i32) {
+// The else branch is needed for zero: without it 0 matches neither condition and nothing is printed
+fn control_flow_example(out: &mut Console, x: i32) -> io::Result<()> {
     if x > 0 {
-        println!("x > 0::x is {}", x);
+        writeln!(out, "x > 0::x is {}", x)?;
     } else if x < 0 {
-        println!("x < 0 :: x is {}", x);
+        writeln!(out, "x < 0 :: x is {}", x)?;
+    } else {
+        writeln!(out, "x == 0 :: x is {}", x)?;
     }
+    Ok(())
 }
-fn loops() {
+pub fn loops(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "------------Loops------------------")?;
     /*
     Rust has three kinds of loops: loop, while, and for. Let’s try each one.
     */
@@ -44,15 +69,16 @@ fn loops() {
             break counter * 2;
         }
     };
-    println!("loop return: {}", ret);
+    writeln!(out, "loop return: {}", ret)?;
 
-    println!("-----------Loop labels---------------");
+    writeln!(out, "-----------Loop labels---------------")?;
+    // basic::control_flow::trace prints every iteration of these loops and which break fired
     let mut counter = 0;
     'counting_loop: loop {
-        println!("loop counter: {}", counter);
+        writeln!(out, "loop counter: {}", counter)?;
         let mut remaining = 10;
         loop {
-            println!("remaining: {}", remaining);
+            writeln!(out, "remaining: {}", remaining)?;
             if remaining == 9 {
                 break;
             }
@@ -65,31 +91,56 @@ fn loops() {
     }
     // While loops
     while counter != 0 {
-        println!(" while loop counter: {}", counter);
+        writeln!(out, " while loop counter: {}", counter)?;
         counter -= 1;
     }
     let a = [10, 20, 30, 40, 50];
     let mut index = 0;
 
     while index < 5 {
-        println!("the value is: {}", a[index]);
+        writeln!(out, "the value is: {}", a[index])?;
 
         index += 1;
     }
-    println!("-----for loop ---------");
+    writeln!(out, "-----for loop ---------")?;
     let a = [10, 20, 30, 40, 50];
 
     for element in a {
-        println!("the value is: {element}");
+        writeln!(out, "the value is: {element}")?;
     }
 
     // Range
-    for number in (0..a.len()){
-        println!("{}", number);
+    for number in 0..a.len() {
+        writeln!(out, "{}", number)?;
     }
     // Index with number
     for (index,num) in a.iter().enumerate(){
-        println!("the value is: {num} at index {}", index);
+        writeln!(out, "the value is: {num} at index {}", index)?;
     }
-
+    Ok(())
 }
+
+// `hello_cargo quiz basic` asks these, see quiz.rs
+pub const QUIZ: &[Question] = &[
+    Question {
+        lesson: "basic::control_flow",
+        function: "control_flow()",
+        prompt: "You type 7. What is y after `let y = if x == 5 { 5 } else { 0 };`?",
+        answer: Answer::Output("0"),
+        explanation: "if is an expression, the value of the branch that runs becomes the value of y.",
+    },
+    Question {
+        lesson: "basic::control_flow::loops",
+        function: "loops()",
+        prompt: "The counter goes up by 1 from 0 and the loop ends with `break counter * 2` at `counter == 10`. What does `loop return: {}` print?",
+        answer: Answer::Output("loop return: 20"),
+        explanation: "The value after break is the value of the loop expression, 10 * 2 here.",
+    },
+    Question {
+        lesson: "basic::control_flow::loops",
+        function: "loops()",
+        prompt: "Inside the inner loop of `'counting_loop: loop { ... loop { ... } }`, which loop does a plain `break` leave?",
+        answer: Answer::Choice(&["Both loops", "The inner loop", "'counting_loop"], 1),
+        explanation: "break leaves the innermost loop, `break 'counting_loop` is needed to leave the outer one.",
+    },
+];
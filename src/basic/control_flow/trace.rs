@@ -0,0 +1,321 @@
+// Loop tracer
+/*
+The same loops as loops(), with every step written to an indented trace:
+each iteration shows the values of the loop variables, nested loops are indented under the loop
+that runs them, and when a break or continue fires the trace says which one and which loop it leaves.
+The loops are copies, a test checks that they print the same lines as loops() does.
+
+    break               leaves the innermost loop
+    break 'label        leaves the loop with that label, and every loop inside it
+    break value         leaves a `loop` and makes value the result of the loop expression
+    continue            skips the rest of this iteration of the innermost loop
+    continue 'label     skips the rest of this iteration of the labelled loop
+
+After the trace, type two numbers to trace the first two loops again with other conditions:
+the counter at which `break counter * 2` fires, and the counter at which `break 'counting_loop` fires.
+*/
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+
+// The conditions of loops(): `counter == 10` and `counter == 2`
+const BREAK_AT: i32 = 10;
+const OUTER_BREAK_AT: i32 = 2;
+
+// Keeps the trace short enough to read
+const MAX_BREAK_AT: i32 = 20;
+const MAX_OUTER_BREAK_AT: i32 = 5;
+
+// Writes trace lines indented by the number of loops they are in
+struct Trace<'a> {
+    out: &'a mut Console,
+    depth: usize,
+}
+
+impl<'a> Trace<'a> {
+    fn new(out: &'a mut Console) -> Self {
+        Trace { out, depth: 0 }
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) -> io::Result<()> {
+        writeln!(self.out, "{}{}", "  ".repeat(self.depth), text.as_ref())
+    }
+
+    // A loop starts, everything traced until leave() belongs to it
+    fn enter(&mut self, header: &str) -> io::Result<()> {
+        self.line(header)?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn iteration(&mut self, number: usize, values: &str) -> io::Result<()> {
+        self.line(format!("iteration {number}: {values}"))
+    }
+
+    // A line the program itself prints, as loops() does
+    fn print(&mut self, text: impl AsRef<str>) -> io::Result<()> {
+        self.line(format!("| {}", text.as_ref()))
+    }
+
+    fn fired(&mut self, statement: &str, effect: &str) -> io::Result<()> {
+        self.line(format!(">> `{statement}` fires: {effect}"))
+    }
+}
+
+pub fn trace(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "------------Loop Tracer------------------")?;
+    writeln!(
+        out,
+        "Lines starting with | are what loops() prints, >> marks a break or continue."
+    )?;
+    let mut trace = Trace::new(out);
+    returning_loop(&mut trace, BREAK_AT)?;
+    labelled_loops(&mut trace, OUTER_BREAK_AT)?;
+    while_loop(&mut trace, OUTER_BREAK_AT)?;
+    for_loop(&mut trace)?;
+    labelled_continue(&mut trace)?;
+
+    loop {
+        writeln!(
+            out,
+            "Trace again with `<break at> <'counting_loop break at>`, e.g. `5 1`, or quit:"
+        )?;
+        let Some(input) = out.read_line()? else {
+            return Ok(());
+        };
+        let input = input.trim();
+        if input == "quit" {
+            return Ok(());
+        }
+        match parse_conditions(input) {
+            Ok((break_at, outer_break_at)) => {
+                let mut trace = Trace::new(out);
+                returning_loop(&mut trace, break_at)?;
+                labelled_loops(&mut trace, outer_break_at)?;
+            }
+            Err(message) => writeln!(out, "{message}")?,
+        }
+    }
+}
+
+// A counter that never reaches the value would loop forever, so the values are checked first
+fn parse_conditions(input: &str) -> Result<(i32, i32), String> {
+    let numbers: Vec<&str> = input.split_whitespace().collect();
+    let [break_at, outer_break_at] = numbers.as_slice() else {
+        return Err("Please type two numbers, e.g. `5 1`.".to_string());
+    };
+    let break_at: i32 = break_at
+        .parse()
+        .map_err(|_| format!("`{break_at}` is not a number."))?;
+    let outer_break_at: i32 = outer_break_at
+        .parse()
+        .map_err(|_| format!("`{outer_break_at}` is not a number."))?;
+    if !(1..=MAX_BREAK_AT).contains(&break_at) {
+        return Err(format!(
+            "The counter starts at 1 and would never reach {break_at}, pick 1 to {MAX_BREAK_AT}."
+        ));
+    }
+    if !(0..=MAX_OUTER_BREAK_AT).contains(&outer_break_at) {
+        return Err(format!(
+            "The counter starts at 0 and would never reach {outer_break_at}, pick 0 to {MAX_OUTER_BREAK_AT}."
+        ));
+    }
+    Ok((break_at, outer_break_at))
+}
+
+fn returning_loop(trace: &mut Trace, break_at: i32) -> io::Result<()> {
+    trace.line("let mut counter = 0;")?;
+    trace.enter("let ret: i32 = loop {")?;
+    let mut counter = 0;
+    let mut iteration = 0;
+    let ret: i32 = loop {
+        iteration += 1;
+        counter += 1;
+        trace.iteration(iteration, &format!("counter += 1 -> counter = {counter}"))?;
+        if counter == break_at {
+            trace.fired(
+                "break counter * 2",
+                &format!(
+                    "counter == {break_at}, leaves the loop with the value {}",
+                    counter * 2
+                ),
+            )?;
+            break counter * 2;
+        }
+    };
+    trace.leave();
+    trace.line(format!("}}; ret = {ret}"))?;
+    trace.print(format!("loop return: {ret}"))
+}
+
+fn labelled_loops(trace: &mut Trace, outer_break_at: i32) -> io::Result<()> {
+    trace.line("let mut counter = 0;")?;
+    trace.enter("'counting_loop: loop {")?;
+    let mut counter = 0;
+    let mut outer = 0;
+    'counting_loop: loop {
+        outer += 1;
+        trace.iteration(outer, &format!("counter = {counter}"))?;
+        trace.print(format!("loop counter: {counter}"))?;
+        let mut remaining = 10;
+        trace.enter("let mut remaining = 10; loop {")?;
+        let mut inner = 0;
+        loop {
+            inner += 1;
+            trace.iteration(
+                inner,
+                &format!("counter = {counter}, remaining = {remaining}"),
+            )?;
+            trace.print(format!("remaining: {remaining}"))?;
+            if remaining == 9 {
+                trace.fired("break", "remaining == 9, leaves the inner loop only")?;
+                break;
+            }
+            if counter == outer_break_at {
+                trace.fired(
+                    "break 'counting_loop",
+                    &format!(
+                        "counter == {outer_break_at}, leaves the inner loop and 'counting_loop"
+                    ),
+                )?;
+                trace.leave();
+                break 'counting_loop;
+            }
+            remaining -= 1;
+            trace.line(format!("remaining -= 1 -> remaining = {remaining}"))?;
+        }
+        trace.leave();
+        trace.line("}")?;
+        counter += 1;
+        trace.line(format!("counter += 1 -> counter = {counter}"))?;
+    }
+    trace.leave();
+    trace.line(format!("}} counter = {counter}"))
+}
+
+fn while_loop(trace: &mut Trace, counter: i32) -> io::Result<()> {
+    let mut counter = counter;
+    trace.enter(&format!("while counter != 0 {{   (counter = {counter})"))?;
+    let mut iteration = 0;
+    while counter != 0 {
+        iteration += 1;
+        trace.iteration(
+            iteration,
+            &format!("counter = {counter}, counter != 0 is true"),
+        )?;
+        trace.print(format!(" while loop counter: {counter}"))?;
+        counter -= 1;
+    }
+    trace.line(format!(
+        "counter = {counter}, counter != 0 is false, the loop ends"
+    ))?;
+    trace.leave();
+    trace.line("}")
+}
+
+fn for_loop(trace: &mut Trace) -> io::Result<()> {
+    let a = [10, 20, 30, 40, 50];
+    trace.line("let a = [10, 20, 30, 40, 50];")?;
+    trace.enter("for (index, num) in a.iter().enumerate() {")?;
+    for (index, num) in a.iter().enumerate() {
+        trace.iteration(index + 1, &format!("index = {index}, num = {num}"))?;
+        trace.print(format!("the value is: {num} at index {index}"))?;
+    }
+    trace.line("the iterator has no more elements, the loop ends")?;
+    trace.leave();
+    trace.line("}")
+}
+
+// loops() has no continue, this one skips to the next row once a row is complete
+fn labelled_continue(trace: &mut Trace) -> io::Result<()> {
+    trace.enter("'rows: for row in 1..=3 {")?;
+    'rows: for row in 1..=3 {
+        trace.iteration(row, &format!("row = {row}"))?;
+        trace.enter("for column in 1..=3 {")?;
+        for column in 1..=3 {
+            trace.iteration(column, &format!("row = {row}, column = {column}"))?;
+            if column > row {
+                trace.fired(
+                    "continue 'rows",
+                    &format!("column {column} > row {row}, skips the rest of this row and goes on with the next row"),
+                )?;
+                trace.leave();
+                continue 'rows;
+            }
+            if column == 2 {
+                trace.fired(
+                    "continue",
+                    "column == 2, skips the print and goes on with the next column",
+                )?;
+                continue;
+            }
+            trace.print(format!("({row}, {column})"))?;
+        }
+        trace.leave();
+        trace.line("}")?;
+    }
+    trace.leave();
+    trace.line("}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basic::control_flow::loops;
+
+    // The `| ` lines a traced loop prints, without the trace around them
+    fn printed(run: impl FnOnce(&mut Trace) -> io::Result<()>) -> Vec<String> {
+        let mut console = Console::buffered();
+        run(&mut Trace::new(&mut console)).unwrap();
+        console
+            .into_output()
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("| "))
+            .map(str::to_string)
+            .collect()
+    }
+
+    // The traced loops are copies of the ones in loops(), this keeps them printing the same lines
+    #[test]
+    fn traced_loops_print_what_loops_prints() {
+        let mut console = Console::buffered();
+        loops(&mut console).unwrap();
+        let output = console.into_output();
+        let lines: Vec<&str> = output.lines().collect();
+        for (name, block) in [
+            (
+                "returning_loop",
+                printed(|trace| returning_loop(trace, BREAK_AT)),
+            ),
+            (
+                "labelled_loops",
+                printed(|trace| labelled_loops(trace, OUTER_BREAK_AT)),
+            ),
+            (
+                "while_loop",
+                printed(|trace| while_loop(trace, OUTER_BREAK_AT)),
+            ),
+            ("for_loop", printed(for_loop)),
+        ] {
+            assert!(!block.is_empty(), "{name}");
+            assert!(
+                lines.windows(block.len()).any(|window| window == block),
+                "{name} prints {block:?}, which loops() does not"
+            );
+        }
+    }
+
+    #[test]
+    fn conditions_that_would_loop_forever_are_rejected() {
+        assert_eq!(parse_conditions("5 1"), Ok((5, 1)));
+        assert!(parse_conditions("0 1").is_err());
+        assert!(parse_conditions("5 -1").is_err());
+        assert!(parse_conditions("five 1").is_err());
+        assert!(parse_conditions("5").is_err());
+    }
+}
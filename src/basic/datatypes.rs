@@ -1,6 +1,17 @@
+pub mod chars;
+pub mod floats;
+pub mod integers;
+pub mod layout;
+pub mod overflow;
 
-pub fn data_types(){
-    println!("--------DataTypes---------");
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::quiz::{Answer, Question};
+
+
+pub fn data_types(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "--------DataTypes---------")?;
 
     // Example 1
     // let guess = "42".trim().parse().expect("Not a number!"); // It shows a error: Must consider giving `guess` an explicit type
@@ -43,6 +54,7 @@ pub fn data_types(){
        must be: 0 to 2^n − 1
        u8 can store numbers from 0 to 2^8 − 1
     */
+    // The lesson basic::datatypes::integers prints this table computed from the real constants
     let sign:i8 = 1;
     let u_sign:u8 = 2;
 
@@ -56,6 +68,7 @@ pub fn data_types(){
     let x = 2.0; // f64
 
     let y: f32 = 3.0; // f32
+    // basic::datatypes::floats shows how both are stored bit by bit
 
     numeric_operations();
 
@@ -70,7 +83,8 @@ pub fn data_types(){
     let c = 'z';
     let z: char = 'ℤ'; // with explicit type annotation
     let heart_eyed_cat = '😻';
-    println!("The char value: {}",heart_eyed_cat);
+    writeln!(out, "The char value: {}",heart_eyed_cat)?;
+    // basic::datatypes::chars shows how these are encoded and stored
 
     // 2. Compound Types: Compound types can group multiple values into one type. Rust has two primitive compound types:
     /*
@@ -83,10 +97,11 @@ pub fn data_types(){
         Each position in the tuple has a type, and the types of the different values in the tuple don’t have to be the same.
     */
     let tup:(i32,bool,char) = (1,true,'a');
-    println!("tup:{:?}",tup);
-    println!("tup2:{}",tup.1);
+    writeln!(out, "tup:{:?}",tup)?;
+    writeln!(out, "tup2:{}",tup.1)?;
+    // basic::datatypes::layout shows how these values are laid out in memory
     let (x, y, z) = tup; // destructuring
-    println!("x:{},y:{},z:{}",x,y,z);
+    writeln!(out, "x:{},y:{},z:{}",x,y,z)?;
 
     // 2. Array: Another way to have a collection of multiple values is with an array.
     /*
@@ -101,12 +116,13 @@ pub fn data_types(){
     */
 
     let a = [1, 2, 3, 4, 5];
-    println!("a[0]: {}",a[0]); // access element based on index array[i]
-    println!("array:{:?}",a);
+    writeln!(out, "a[0]: {}",a[0])?; // access element based on index array[i]
+    writeln!(out, "array:{:?}",a)?;
 
     let a: [i32; 5] = [1, 2, 3, 4, 5];
     let a = [3; 5]; // Initialize an array with 3's has length 5
-    println!("a::: {:?}",a);
+    writeln!(out, "a::: {:?}",a)?;
+    Ok(())
 }
 fn numeric_operations(){
     //addition
@@ -123,4 +139,31 @@ fn numeric_operations(){
     let truncated = -5 / 3; // Results in -1
 
     let remainder = 5%4;
-}
\ No newline at end of file
+
+    // These all fit easily, basic::datatypes::overflow shows what happens at the edges of a type
+}
+
+// `hello_cargo quiz basic` asks these, see quiz.rs
+pub const QUIZ: &[Question] = &[
+    Question {
+        lesson: "basic::datatypes",
+        function: "numeric_operations()",
+        prompt: "What does `println!(\"{}\", -5 / 3)` print?",
+        answer: Answer::Output("-1"),
+        explanation: "Integer division truncates toward zero: -1.67 becomes -1, not -2.",
+    },
+    Question {
+        lesson: "basic::datatypes",
+        function: "numeric_operations()",
+        prompt: "What does `println!(\"{}\", 5 % 4)` print?",
+        answer: Answer::Output("1"),
+        explanation: "% is the remainder of the integer division, 5 = 1 * 4 + 1.",
+    },
+    Question {
+        lesson: "basic::datatypes",
+        function: "numeric_operations()",
+        prompt: "What is the type of `let quotient = 56.7 / 32.2;`?",
+        answer: Answer::Choice(&["f32", "f64", "i32"], 1),
+        explanation: "A floating point literal without a suffix is an f64, so the division is one too.",
+    },
+];
@@ -0,0 +1,165 @@
+// Unicode character inspector
+/*
+data_types() declares three chars: 'z', 'ℤ' and '😻'. A char is a Unicode scalar value,
+a code point from U+0000 to U+10FFFF (minus the surrogates U+D800 to U+DFFF), and always takes 4 bytes.
+
+Text is stored differently. A String holds UTF-8, where a character takes 1 to 4 bytes:
+    >. U+0000  to U+007F    1 byte    (ASCII, 'z')
+    >. U+0080  to U+07FF    2 bytes   ('é')
+    >. U+0800  to U+FFFF    3 bytes   ('ℤ')
+    >. U+10000 to U+10FFFF  4 bytes   ('😻')
+UTF-16, used by Windows and JavaScript, needs one 16-bit unit below U+10000 and a surrogate pair above.
+
+Every char also has a Unicode general category such as Lu (uppercase letter) or So (other symbol),
+which is what methods like is_alphabetic() and is_numeric() are built on.
+
+Type some text, or a code point like U+1F63B, to inspect each of its characters.
+*/
+
+use std::io::{self, Write};
+use std::mem::size_of;
+
+use unicode_general_category::get_general_category;
+
+use crate::console::Console;
+
+// Characters inspected per line of input, so a pasted paragraph does not flood the screen
+const MAX_CHARS: usize = 8;
+
+pub fn chars(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "--------Characters---------")?;
+    let c = 'z';
+    let z: char = 'ℤ';
+    let heart_eyed_cat = '😻';
+    for ch in [c, z, heart_eyed_cat] {
+        inspect(out, ch)?;
+    }
+
+    writeln!(out, "-----char versus String-----")?;
+    let chars = [c, z, heart_eyed_cat];
+    let text = String::from_iter(chars);
+    writeln!(
+        out,
+        "size_of::<char>() is {} bytes, so [char; 3] takes {} bytes whatever the characters are.",
+        size_of::<char>(),
+        size_of::<[char; 3]>()
+    )?;
+    writeln!(
+        out,
+        "The String {text:?} holds the same 3 chars in {} bytes of UTF-8:",
+        text.len()
+    )?;
+    for (index, ch) in text.char_indices() {
+        writeln!(
+            out,
+            "  byte {index}..{}: {ch:?} {}",
+            index + ch.len_utf8(),
+            utf8_bytes(ch)
+        )?;
+    }
+    writeln!(
+        out,
+        "text.len() counts bytes ({}), text.chars().count() counts chars ({}).",
+        text.len(),
+        text.chars().count()
+    )?;
+
+    writeln!(
+        out,
+        "Type some text, or a code point like U+00E9, to inspect it, or `quit` to stop."
+    )?;
+    while let Some(input) = out.read_line()? {
+        if input.trim() == "quit" {
+            break;
+        }
+        if let Some(hex) = input
+            .trim()
+            .strip_prefix("U+")
+            .or(input.trim().strip_prefix("u+"))
+        {
+            match u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+                Some(ch) => inspect(out, ch)?,
+                None => writeln!(
+                    out,
+                    "U+{hex} is not a Unicode scalar value, chars exclude the surrogates and stop at U+10FFFF"
+                )?,
+            }
+            continue;
+        }
+        for ch in input.chars().take(MAX_CHARS) {
+            inspect(out, ch)?;
+        }
+        if input.chars().count() > MAX_CHARS {
+            writeln!(out, "(only the first {MAX_CHARS} characters are shown)")?;
+        }
+    }
+    Ok(())
+}
+
+fn inspect(out: &mut Console, ch: char) -> io::Result<()> {
+    let category = get_general_category(ch);
+    let mut utf16 = [0; 2];
+    let utf16: Vec<String> = ch
+        .encode_utf16(&mut utf16)
+        .iter()
+        .map(|unit| format!("{unit:04X}"))
+        .collect();
+
+    writeln!(out, "{ch:?} U+{:04X}", ch as u32)?;
+    writeln!(
+        out,
+        "  UTF-8:    {} ({} byte{})",
+        utf8_bytes(ch),
+        ch.len_utf8(),
+        if ch.len_utf8() == 1 { "" } else { "s" }
+    )?;
+    writeln!(
+        out,
+        "  UTF-16:   {}{}",
+        utf16.join(" "),
+        if utf16.len() == 2 {
+            " (surrogate pair)"
+        } else {
+            ""
+        }
+    )?;
+    writeln!(
+        out,
+        "  category: {} ({category:?})",
+        category.abbreviation()
+    )?;
+    let predicates = [
+        ("alphabetic", ch.is_alphabetic()),
+        ("numeric", ch.is_numeric()),
+        ("alphanumeric", ch.is_alphanumeric()),
+        ("whitespace", ch.is_whitespace()),
+        ("uppercase", ch.is_uppercase()),
+        ("lowercase", ch.is_lowercase()),
+        ("control", ch.is_control()),
+        ("ascii", ch.is_ascii()),
+    ];
+    let yes: Vec<&str> = predicates
+        .iter()
+        .filter(|(_, holds)| *holds)
+        .map(|(name, _)| *name)
+        .collect();
+    writeln!(
+        out,
+        "  is_*:     {}",
+        if yes.is_empty() {
+            "none".to_string()
+        } else {
+            yes.join(", ")
+        }
+    )?;
+    Ok(())
+}
+
+fn utf8_bytes(ch: char) -> String {
+    let mut buffer = [0; 4];
+    ch.encode_utf8(&mut buffer)
+        .bytes()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,220 @@
+// IEEE-754 floating-point inspector
+/*
+data_types() declares `let x = 2.0;` (f64) and `let y: f32 = 3.0;` (f32). Both are stored in the
+IEEE-754 binary format as three bit fields:
+
+    f32:  1 sign bit | 8 exponent bits  | 23 mantissa bits
+    f64:  1 sign bit | 11 exponent bits | 52 mantissa bits
+
+    value = (-1)^sign * 1.mantissa * 2^(exponent - bias)     bias is 127 for f32, 1023 for f64
+
+Special exponents give the special values:
+    >. exponent all zeros, mantissa zero        -> +0.0 or -0.0
+    >. exponent all zeros, mantissa not zero    -> subnormal, 0.mantissa * 2^(1 - bias), tiny numbers with less precision
+    >. exponent all ones, mantissa zero         -> +inf or -inf
+    >. exponent all ones, mantissa not zero     -> NaN, which is not even equal to itself
+
+Only numbers that are a sum of powers of two are exact. 56.7 and 32.2 are not, so the `quotient`
+in numeric_operations() divides two already rounded numbers and rounds the result once more.
+
+Type a number (optionally prefixed with f32 or f64) to see how it is stored.
+*/
+
+use std::io::{self, Write};
+use std::num::FpCategory;
+
+use crate::console::Console;
+
+// Longer exact decimal expansions are shown in scientific notation
+const MAX_DIGITS: usize = 60;
+
+pub struct Inspection {
+    pub ty: &'static str,
+    pub value: String,
+    pub sign: u64,
+    pub exponent: u64,
+    pub mantissa: u64,
+    pub exponent_bits: u32,
+    pub mantissa_bits: u32,
+    pub bias: i32,
+    pub category: FpCategory,
+    pub previous: String,
+    pub next: String,
+}
+
+macro_rules! inspect_fn {
+    ($name:ident, $t:ty, $exponent_bits:expr, $mantissa_bits:expr) => {
+        pub fn $name(x: $t) -> Inspection {
+            let bits = x.to_bits() as u64;
+            Inspection {
+                ty: stringify!($t),
+                value: exact(x as f64),
+                sign: bits >> ($exponent_bits + $mantissa_bits),
+                exponent: (bits >> $mantissa_bits) & ((1 << $exponent_bits) - 1),
+                mantissa: bits & ((1 << $mantissa_bits) - 1),
+                exponent_bits: $exponent_bits,
+                mantissa_bits: $mantissa_bits,
+                bias: (1 << ($exponent_bits - 1)) - 1,
+                category: x.classify(),
+                previous: exact(x.next_down() as f64),
+                next: exact(x.next_up() as f64),
+            }
+        }
+    };
+}
+
+inspect_fn!(inspect_f32, f32, 8, 23);
+inspect_fn!(inspect_f64, f64, 11, 52);
+
+impl Inspection {
+    pub fn bit_fields(&self) -> String {
+        format!(
+            "{} | {:0ew$b} | {:0mw$b}",
+            self.sign,
+            self.exponent,
+            self.mantissa,
+            ew = self.exponent_bits as usize,
+            mw = self.mantissa_bits as usize
+        )
+    }
+
+    // The power of two the mantissa is scaled by, None for infinities and NaN
+    pub fn scale(&self) -> Option<i32> {
+        match self.category {
+            FpCategory::Nan | FpCategory::Infinite => None,
+            FpCategory::Zero | FpCategory::Subnormal => Some(1 - self.bias),
+            FpCategory::Normal => Some(self.exponent as i32 - self.bias),
+        }
+    }
+}
+
+// Every finite float is a fraction with a power of two below it, so it has a finite decimal expansion.
+// Formatting with enough digits prints all of it, the trailing zeros are then dropped.
+pub fn exact(x: f64) -> String {
+    if !x.is_finite() {
+        return x.to_string();
+    }
+    let digits = format!("{x:.1100}");
+    let digits = digits.trim_end_matches('0').trim_end_matches('.');
+    if digits.len() > MAX_DIGITS {
+        let count = digits.chars().filter(char::is_ascii_digit).count();
+        format!("{x:e} (the exact decimal has {count} digits)")
+    } else {
+        digits.to_string()
+    }
+}
+
+pub fn floats(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "--------Floating-Point Numbers---------")?;
+    let x = 2.0;
+    let y: f32 = 3.0;
+    print_inspection(out, &inspect_f64(x))?;
+    print_inspection(out, &inspect_f32(y))?;
+
+    writeln!(out, "-----Special values-----")?;
+    for value in [-0.0, f64::MIN_POSITIVE / 4.0, f64::INFINITY, f64::NAN] {
+        print_inspection(out, &inspect_f64(value))?;
+    }
+    let nan = f64::NAN;
+    #[allow(clippy::eq_op, invalid_nan_comparisons)] // Comparing NaN to itself is the point here
+    let nan_equals_itself = nan == nan;
+    writeln!(
+        out,
+        "NaN == NaN: {nan_equals_itself}, test for NaN with is_nan() instead"
+    )?;
+
+    writeln!(out, "-----Why 56.7 / 32.2 is not exact-----")?;
+    let quotient = 56.7 / 32.2;
+    writeln!(out, "56.7 is stored as     {}", exact(56.7))?;
+    writeln!(out, "32.2 is stored as     {}", exact(32.2))?;
+    writeln!(out, "56.7 / 32.2 prints as {quotient}")?;
+    writeln!(out, "and is stored as      {}", exact(quotient))?;
+    writeln!(
+        out,
+        "while 567 / 322 is    1.76086956521739130434782608695652173913..."
+    )?;
+    writeln!(
+        out,
+        "Neither input is exact, and the division rounds its result to the nearest f64 once more."
+    )?;
+    writeln!(
+        out,
+        "The same happens with 0.1 + 0.2 == 0.3, which is {}: 0.1 + 0.2 is {}",
+        0.1 + 0.2 == 0.3,
+        0.1 + 0.2
+    )?;
+    writeln!(
+        out,
+        "Compare floats with a tolerance instead: (0.1 + 0.2 - 0.3).abs() < f64::EPSILON is {}",
+        (0.1_f64 + 0.2 - 0.3).abs() < f64::EPSILON
+    )?;
+
+    writeln!(
+        out,
+        "Type a number, e.g. `0.1`, `f32 16777217` or `-inf`, to inspect it, or `quit` to stop."
+    )?;
+    while let Some(input) = out.read_line()? {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let (types, number) = match words.as_slice() {
+            ["quit"] => break,
+            ["f32", number] => (&["f32"][..], *number),
+            ["f64", number] => (&["f64"][..], *number),
+            [number] => (&["f32", "f64"][..], *number),
+            _ => {
+                writeln!(
+                    out,
+                    "Expected a number, optionally prefixed with f32 or f64"
+                )?;
+                continue;
+            }
+        };
+        for ty in types {
+            let inspection = if *ty == "f32" {
+                number.parse::<f32>().map(inspect_f32)
+            } else {
+                number.parse::<f64>().map(inspect_f64)
+            };
+            match inspection {
+                Ok(inspection) => print_inspection(out, &inspection)?,
+                Err(err) => writeln!(out, "`{number}` is not a valid {ty}: {err}")?,
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_inspection(out: &mut Console, inspection: &Inspection) -> io::Result<()> {
+    writeln!(out, "{} {}", inspection.ty, inspection.value)?;
+    writeln!(out, "  bits:     {}", inspection.bit_fields())?;
+    match (inspection.category, inspection.scale()) {
+        (FpCategory::Normal, Some(scale)) => writeln!(
+            out,
+            "  normal:   (-1)^{} * 1.mantissa * 2^({} - {}) = 2^{scale} scale",
+            inspection.sign, inspection.exponent, inspection.bias
+        )?,
+        (FpCategory::Subnormal, Some(scale)) => writeln!(
+            out,
+            "  subnormal: exponent bits are all zero, (-1)^{} * 0.mantissa * 2^{scale}",
+            inspection.sign
+        )?,
+        (FpCategory::Zero, _) => writeln!(
+            out,
+            "  zero:     {} zero, all exponent and mantissa bits are zero",
+            if inspection.sign == 1 {
+                "negative"
+            } else {
+                "positive"
+            }
+        )?,
+        (FpCategory::Infinite, _) => {
+            writeln!(out, "  infinite: exponent bits all one, mantissa zero")?
+        }
+        (category, _) => writeln!(
+            out,
+            "  {category:?}: exponent bits all one, mantissa not zero"
+        )?,
+    }
+    writeln!(out, "  previous: {}", inspection.previous)?;
+    writeln!(out, "  next:     {}", inspection.next)?;
+    Ok(())
+}
@@ -0,0 +1,163 @@
+// Integer type explorer
+/*
+The table in data_types() lists the integer types and their ranges by hand.
+Here the same table is computed from the constants every integer type carries:
+    i8::MIN, i8::MAX  -> smallest and largest value
+    i8::BITS          -> width in bits
+    size_of::<i8>()   -> bytes the value takes in memory
+    align_of::<i8>()  -> the address of a value must be a multiple of this
+
+isize and usize have the width of a pointer, 64 bits on a 64-bit machine and 32 bits on a 32-bit one.
+
+Afterwards type in a number to see every integer type that can hold it.
+*/
+
+use std::io::{self, Write};
+use std::mem::{align_of, size_of};
+
+use crate::console::Console;
+
+pub struct IntegerType {
+    pub name: &'static str,
+    pub bits: u32,
+    pub size: usize,
+    pub align: usize,
+    // Widened so that one field can hold the limits of every type, including i128 and u128
+    pub min: i128,
+    pub max: u128,
+}
+
+impl IntegerType {
+    pub fn is_signed(&self) -> bool {
+        self.min < 0
+    }
+
+    pub fn holds(&self, value: Number) -> bool {
+        match value {
+            Number::Negative(n) => n >= self.min,
+            Number::NonNegative(n) => n <= self.max,
+        }
+    }
+}
+
+// A typed number, kept in the widest type of its sign
+#[derive(Clone, Copy)]
+pub enum Number {
+    Negative(i128),
+    NonNegative(u128),
+}
+
+macro_rules! integer_types {
+    ($($t:ty),*) => {
+        [$(IntegerType {
+            name: stringify!($t),
+            bits: <$t>::BITS,
+            size: size_of::<$t>(),
+            align: align_of::<$t>(),
+            min: <$t>::MIN as i128,
+            max: <$t>::MAX as u128,
+        }),*]
+    };
+}
+
+pub fn integer_types() -> [IntegerType; 12] {
+    integer_types!(
+        i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize
+    )
+}
+
+pub fn parse_number(input: &str) -> Result<Number, String> {
+    let input = input.trim().replace('_', "");
+    let parsed = if input.starts_with('-') {
+        input.parse::<i128>().map(|n| {
+            if n < 0 {
+                Number::Negative(n)
+            } else {
+                // -0
+                Number::NonNegative(0)
+            }
+        })
+    } else {
+        input.parse::<u128>().map(Number::NonNegative)
+    };
+    parsed.map_err(|err| err.to_string())
+}
+
+pub fn integers(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "--------Integer Types---------")?;
+    let types = integer_types();
+    writeln!(
+        out,
+        "{:<6}| {:>4} | {:>4} | {:>5} | {:>40} | {:>39}",
+        "type", "bits", "size", "align", "min", "max"
+    )?;
+    writeln!(out, "{}", "-".repeat(112))?;
+    for int in &types {
+        writeln!(
+            out,
+            "{:<6}| {:>4} | {:>4} | {:>5} | {:>40} | {:>39}",
+            int.name, int.bits, int.size, int.align, int.min, int.max
+        )?;
+    }
+
+    writeln!(
+        out,
+        "isize and usize have the width of a pointer, {} bits on this machine.",
+        usize::BITS
+    )?;
+    writeln!(
+        out,
+        "Signed types hold -(2^(n-1)) to 2^(n-1) - 1, unsigned types hold 0 to 2^n - 1."
+    )?;
+    writeln!(
+        out,
+        "Type a number to see which types can hold it, or `quit` to stop."
+    )?;
+    while let Some(input) = out.read_line()? {
+        if input.trim() == "quit" {
+            break;
+        }
+        let number = match parse_number(&input) {
+            Ok(number) => number,
+            Err(err) => {
+                writeln!(
+                    out,
+                    "`{}` cannot be read as an integer: {err}",
+                    input.trim()
+                )?;
+                continue;
+            }
+        };
+        let fits: Vec<&str> = types
+            .iter()
+            .filter(|int| int.holds(number))
+            .map(|int| int.name)
+            .collect();
+        let smallest_signed = types
+            .iter()
+            .find(|int| int.is_signed() && int.holds(number));
+        let smallest_unsigned = types
+            .iter()
+            .find(|int| !int.is_signed() && int.holds(number));
+        writeln!(out, "{} fits in: {}", input.trim(), fits.join(", "))?;
+        match (smallest_signed, smallest_unsigned) {
+            (Some(signed), Some(unsigned)) => writeln!(
+                out,
+                "Smallest signed type: {}, smallest unsigned type: {}",
+                signed.name, unsigned.name
+            )?,
+            (Some(signed), None) => writeln!(
+                out,
+                "Negative numbers need a signed type, the smallest is {}",
+                signed.name
+            )?,
+            (None, Some(unsigned)) => writeln!(
+                out,
+                "Too large for every signed type, only {} can hold it",
+                unsigned.name
+            )?,
+            (None, None) => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,274 @@
+// Memory layout of tuples and arrays
+/*
+data_types() prints `tup: (i32, bool, char)` and `a: [i32; 5]` but not how they sit in memory.
+std::mem answers that:
+    >. size_of::<T>()        bytes a value of T occupies, including padding
+    >. align_of::<T>()       the address of a T must be a multiple of this
+    >. offset_of!(T, field)  where a field starts, counted in bytes from the start of the value
+
+Every field must be placed at a multiple of its own alignment, and the total size is rounded up
+to a multiple of the largest alignment so that values stay aligned when placed in an array.
+The gaps this leaves are padding. That is why (i32, bool, char) needs 12 bytes although its
+fields only add up to 4 + 1 + 4 = 9.
+
+Rust is free to reorder the fields of a tuple or struct to save padding, and which order it picks
+may change between compiler versions. Only #[repr(C)] types keep the declared order, the way C lays out
+structs, so every tuple is drawn twice: as this compiler lays it out, offset_of! works on tuples too,
+and as a #[repr(C)] tuple struct with the same fields. The snapshot masks the first, see snapshots.rs.
+
+Array elements follow each other without gaps: element i starts at i * size_of::<T>().
+An element with padding inside brings that padding along into the array.
+*/
+
+use std::io::{self, Write};
+use std::mem::{align_of, offset_of, size_of};
+
+use crate::console::Console;
+use crate::snapshots::COMPILER_DEPENDENT;
+
+pub struct Field {
+    pub name: String,
+    pub ty: &'static str,
+    pub offset: usize,
+    pub size: usize,
+    // Padding inside the field itself, like the padding of a tuple that is an array element
+    pub padding: usize,
+}
+
+pub struct Layout {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+    pub fields: Vec<Field>,
+}
+
+impl Layout {
+    // Bytes between and after the fields, plus the padding inside the fields
+    pub fn padding(&self) -> usize {
+        let fields = self.fields.iter();
+        self.size - fields.clone().map(|field| field.size).sum::<usize>()
+            + fields.map(|field| field.padding).sum::<usize>()
+    }
+
+    // `len` of these values one after the other, each element keeping its own padding
+    pub fn array(&self, ty: &'static str, len: usize) -> Layout {
+        Layout {
+            name: format!("[{ty}; {len}]"),
+            size: self.size * len,
+            align: self.align,
+            fields: (0..len)
+                .map(|i| Field {
+                    name: format!("[{i}]"),
+                    ty,
+                    offset: i * self.size,
+                    size: self.size,
+                    padding: self.padding(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// layout_of!(name, T => 0: i32, 1: bool) for a T whose fields are .0: i32 and .1: bool
+macro_rules! layout_of {
+    ($name:expr, $ty:ty => $($index:tt : $t:ty),+) => {
+        Layout {
+            name: $name,
+            size: size_of::<$ty>(),
+            align: align_of::<$ty>(),
+            fields: vec![$(Field {
+                name: format!(".{}", $index),
+                ty: stringify!($t),
+                offset: offset_of!($ty, $index),
+                size: size_of::<$t>(),
+                padding: 0,
+            }),+],
+        }
+    };
+}
+
+// struct_layout!(Tup => 0: i32, 1: bool, 2: char) for `#[repr(C)] struct Tup(i32, bool, char)`
+macro_rules! struct_layout {
+    ($name:ident => $($index:tt : $t:ty),+) => {
+        layout_of!(
+            format!(
+                "#[repr(C)] struct {}({})",
+                stringify!($name),
+                [$(stringify!($t)),+].join(", ")
+            ),
+            $name => $($index: $t),+
+        )
+    };
+}
+
+// tuple_layout!(0: i32, 1: bool, 2: char) for the tuple (i32, bool, char), in the order this compiler picks
+macro_rules! tuple_layout {
+    ($($index:tt : $t:ty),+) => {{
+        type Tuple = ($($t,)+);
+        layout_of!(
+            format!("({}){COMPILER_DEPENDENT}", [$(stringify!($t)),+].join(", ")),
+            Tuple => $($index: $t),+
+        )
+    }};
+}
+
+// array_layout!(i32; 5)
+macro_rules! array_layout {
+    ($t:ty; $len:expr) => {
+        Layout {
+            name: stringify!([$t; $len]).to_string(),
+            size: size_of::<[$t; $len]>(),
+            align: align_of::<[$t; $len]>(),
+            fields: (0..$len)
+                .map(|i| Field {
+                    name: format!("[{i}]"),
+                    ty: stringify!($t),
+                    offset: i * size_of::<$t>(),
+                    size: size_of::<$t>(),
+                    padding: 0,
+                })
+                .collect(),
+        }
+    };
+}
+
+// The tuples of the lesson as tuple structs in declaration order
+#[repr(C)]
+struct Tup(i32, bool, char);
+#[repr(C)]
+struct Spread(u8, u32, u8);
+#[repr(C)]
+struct Sorted(u32, u8, u8);
+#[repr(C)]
+struct Small(u8, u16);
+#[repr(C)]
+struct Pair(u8, u32);
+
+pub fn layout(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "--------Memory Layout---------")?;
+    let tup: (i32, bool, char) = (1, true, 'a');
+    writeln!(out, "tup:{:?}", tup)?;
+    print_layout(out, &tuple_layout!(0: i32, 1: bool, 2: char))?;
+    print_layout(out, &struct_layout!(Tup => 0: i32, 1: bool, 2: char))?;
+
+    writeln!(out, "-----Field order-----")?;
+    print_layout(out, &struct_layout!(Spread => 0: u8, 1: u32, 2: u8))?;
+    print_layout(out, &struct_layout!(Sorted => 0: u32, 1: u8, 2: u8))?;
+    writeln!(
+        out,
+        "The tuple (u8, u32, u8) has no #[repr(C)], so Rust may reorder its fields like Sorted to save padding:"
+    )?;
+    print_layout(out, &tuple_layout!(0: u8, 1: u32, 2: u8))?;
+    print_layout(out, &struct_layout!(Small => 0: u8, 1: u16))?;
+
+    writeln!(out, "-----Arrays-----")?;
+    let a: [i32; 5] = [1, 2, 3, 4, 5];
+    writeln!(out, "array:{:?}", a)?;
+    print_layout(out, &array_layout!(i32; 5))?;
+    print_layout(out, &array_layout!(u8; 3))?;
+    let pair = struct_layout!(Pair => 0: u8, 1: u32);
+    print_layout(out, &pair)?;
+    let pairs = pair.array("Pair", 2);
+    print_layout(out, &pairs)?;
+    let data: usize = pair.fields.iter().map(|field| field.size).sum();
+    writeln!(
+        out,
+        "Each Pair carries its own {} of padding after {} of data, so the array has {} of padding in total.",
+        bytes(pair.padding()),
+        bytes(data),
+        bytes(pairs.padding())
+    )?;
+    Ok(())
+}
+
+fn print_layout(out: &mut Console, layout: &Layout) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}: size {}, align {}, fields {}, padding {}",
+        layout.name,
+        layout.size,
+        layout.align,
+        bytes(layout.size - layout.padding()),
+        bytes(layout.padding())
+    )?;
+
+    // Fields in memory order, each remembering its declared position for the byte map
+    let mut fields: Vec<(usize, &Field)> = layout.fields.iter().enumerate().collect();
+    fields.sort_by_key(|(_, field)| field.offset);
+    // Byte map: one cell per byte, the field's declared position for its bytes and _ for padding
+    let mut cells = Vec::new();
+    let mut position = 0;
+    for (number, field) in fields {
+        print_padding(out, &mut cells, position, field.offset)?;
+        print_bytes(
+            out,
+            (field.offset, field.offset + field.size),
+            &field.name,
+            field.ty,
+        )?;
+        if field.padding > 0 {
+            writeln!(
+                out,
+                "         {} of them padding inside {}",
+                field.padding, field.name
+            )?;
+        }
+        cells.extend(std::iter::repeat_n((number % 10).to_string(), field.size));
+        position = field.offset + field.size;
+    }
+    print_padding(out, &mut cells, position, layout.size)?;
+    writeln!(out, "  bytes: [{}]", cells.join(" "))
+}
+
+fn print_padding(
+    out: &mut Console,
+    cells: &mut Vec<String>,
+    from: usize,
+    to: usize,
+) -> io::Result<()> {
+    if to > from {
+        print_bytes(out, (from, to), "padding", "")?;
+        cells.extend(std::iter::repeat_n("_".to_string(), to - from));
+    }
+    Ok(())
+}
+
+fn print_bytes(
+    out: &mut Console,
+    (from, to): (usize, usize),
+    name: &str,
+    ty: &str,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "  {from:>2}..{to:<2} {name:<10} {ty:<9} {}",
+        bytes(to - from)
+    )
+}
+
+fn bytes(count: usize) -> String {
+    format!("{count} byte{}", if count == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The snapshot masks the tuples as the compiler lays them out, so their fields are checked here
+    #[test]
+    fn tuple_fields_fit_without_overlapping() {
+        for tuple in [
+            tuple_layout!(0: i32, 1: bool, 2: char),
+            tuple_layout!(0: u8, 1: u32, 2: u8),
+        ] {
+            let mut fields: Vec<&Field> = tuple.fields.iter().collect();
+            fields.sort_by_key(|field| field.offset);
+            let mut end = 0;
+            for field in fields {
+                assert!(field.offset >= end, "{}", tuple.name);
+                end = field.offset + field.size;
+            }
+            assert!(end <= tuple.size, "{}", tuple.name);
+        }
+    }
+}
@@ -0,0 +1,369 @@
+// Integer overflow laboratory
+/*
+numeric_operations() only adds and divides small numbers. Every integer type has an edge though,
+and `i8::MAX + 1` does not fit in an i8. What happens depends on how the program was built:
+    >. Debug build (cargo run): the program panics with "attempt to add with overflow".
+    >. Release build (cargo run --release): no check is made and the value wraps around,
+       127_i8 + 1 becomes -128, the same as two's complement hardware does.
+
+Relying on either behaviour is a bug, so the standard library offers four explicit families of methods:
+    >. wrapping_*    wrap around in every build            127_i8.wrapping_add(1)    == -128
+    >. checked_*     return None on overflow               127_i8.checked_add(1)     == None
+    >. saturating_*  clamp to the type's MIN or MAX        127_i8.saturating_add(1)  == 127
+    >. overflowing_* return the wrapped value and a flag   127_i8.overflowing_add(1) == (-128, true)
+
+Division is special: dividing by zero panics in every build and with every method except checked_div,
+and MIN / -1 is the one division that overflows, because -MIN is one larger than MAX.
+Unlike the other operators, an overflowing `/` or `%` panics in release builds as well.
+
+Type a calculation as `<type> <a> <op> <b>`, e.g. `u8 0 - 1`, to see all four side by side.
+*/
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crate::console::Console;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Op {
+    pub fn parse(symbol: &str) -> Option<Op> {
+        match symbol {
+            "+" => Some(Op::Add),
+            "-" => Some(Op::Sub),
+            "*" => Some(Op::Mul),
+            "/" => Some(Op::Div),
+            "%" => Some(Op::Rem),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+        }
+    }
+
+    // Suffix of the method names, e.g. wrapping_add
+    fn method(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+        }
+    }
+
+    // The word used in the debug build panic message, e.g. "attempt to add with overflow"
+    fn verb(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "subtract",
+            Op::Mul => "multiply",
+            Op::Div => "divide",
+            Op::Rem => "calculate the remainder",
+        }
+    }
+}
+
+// What every variant of an operation produced, rendered as text so all types share one shape
+#[derive(Debug, PartialEq)]
+pub struct Outcome {
+    pub wrapping: String,
+    pub checked: String,
+    pub saturating: String,
+    pub overflowing: String,
+    pub overflowed: bool,
+    pub divide_by_zero: bool,
+}
+
+const PANICS: &str = "panics";
+
+impl Outcome {
+    fn new<T: Display>(
+        wrapping: T,
+        checked: Option<T>,
+        saturating: Option<T>,
+        (value, overflowed): (T, bool),
+    ) -> Outcome {
+        Outcome {
+            wrapping: wrapping.to_string(),
+            checked: match checked {
+                Some(n) => format!("Some({n})"),
+                None => "None".to_string(),
+            },
+            // There is no saturating remainder: the remainder of a division cannot exceed the divisor
+            saturating: saturating.map_or("-".to_string(), |n| n.to_string()),
+            overflowing: format!("({value}, {overflowed})"),
+            overflowed,
+            divide_by_zero: false,
+        }
+    }
+
+    fn divide_by_zero(op: Op) -> Outcome {
+        Outcome {
+            wrapping: PANICS.to_string(),
+            checked: "None".to_string(),
+            saturating: if op == Op::Rem { "-" } else { PANICS }.to_string(),
+            overflowing: PANICS.to_string(),
+            overflowed: false,
+            divide_by_zero: true,
+        }
+    }
+}
+
+macro_rules! evaluate_as {
+    ($t:ty, $a:expr, $op:expr, $b:expr) => {{
+        let a: $t = $a
+            .parse()
+            .map_err(|err| format!("`{}` is not a valid {}: {err}", $a, stringify!($t)))?;
+        let b: $t = $b
+            .parse()
+            .map_err(|err| format!("`{}` is not a valid {}: {err}", $b, stringify!($t)))?;
+        Ok(match $op {
+            Op::Add => Outcome::new(
+                a.wrapping_add(b),
+                a.checked_add(b),
+                Some(a.saturating_add(b)),
+                a.overflowing_add(b),
+            ),
+            Op::Sub => Outcome::new(
+                a.wrapping_sub(b),
+                a.checked_sub(b),
+                Some(a.saturating_sub(b)),
+                a.overflowing_sub(b),
+            ),
+            Op::Mul => Outcome::new(
+                a.wrapping_mul(b),
+                a.checked_mul(b),
+                Some(a.saturating_mul(b)),
+                a.overflowing_mul(b),
+            ),
+            Op::Div | Op::Rem if b == 0 => Outcome::divide_by_zero($op),
+            Op::Div => Outcome::new(
+                a.wrapping_div(b),
+                a.checked_div(b),
+                Some(a.saturating_div(b)),
+                a.overflowing_div(b),
+            ),
+            Op::Rem => Outcome::new(
+                a.wrapping_rem(b),
+                a.checked_rem(b),
+                None,
+                a.overflowing_rem(b),
+            ),
+        })
+    }};
+}
+
+pub fn evaluate(ty: &str, a: &str, op: Op, b: &str) -> Result<Outcome, String> {
+    match ty {
+        "i8" => evaluate_as!(i8, a, op, b),
+        "u8" => evaluate_as!(u8, a, op, b),
+        "i16" => evaluate_as!(i16, a, op, b),
+        "u16" => evaluate_as!(u16, a, op, b),
+        "i32" => evaluate_as!(i32, a, op, b),
+        "u32" => evaluate_as!(u32, a, op, b),
+        "i64" => evaluate_as!(i64, a, op, b),
+        "u64" => evaluate_as!(u64, a, op, b),
+        "i128" => evaluate_as!(i128, a, op, b),
+        "u128" => evaluate_as!(u128, a, op, b),
+        "isize" => evaluate_as!(isize, a, op, b),
+        "usize" => evaluate_as!(usize, a, op, b),
+        other => Err(format!("`{other}` is not an integer type")),
+    }
+}
+
+pub fn overflow(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "--------Integer Overflow---------")?;
+    for (ty, a, op, b) in [
+        ("i8", "127", Op::Add, "1"),
+        ("u8", "0", Op::Sub, "1"),
+        ("u8", "200", Op::Mul, "2"),
+        ("i32", "-2147483648", Op::Div, "-1"),
+        ("u8", "7", Op::Rem, "0"),
+        ("i32", "-5", Op::Div, "3"),
+    ] {
+        let outcome = evaluate(ty, a, op, b).expect("demo calculations are valid");
+        print_outcome(out, ty, a, op, b, &outcome)?;
+    }
+
+    writeln!(
+        out,
+        "Type a calculation as `<type> <a> <op> <b>`, e.g. `i16 300 * 200`, or `quit` to stop."
+    )?;
+    while let Some(input) = out.read_line()? {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        match words.as_slice() {
+            ["quit"] => break,
+            [ty, a, symbol, b] => {
+                let Some(op) = Op::parse(symbol) else {
+                    writeln!(out, "`{symbol}` is not one of +, -, *, /, %")?;
+                    continue;
+                };
+                match evaluate(ty, a, op, b) {
+                    Ok(outcome) => print_outcome(out, ty, a, op, b, &outcome)?,
+                    Err(err) => writeln!(out, "{err}")?,
+                }
+            }
+            _ => writeln!(out, "Expected `<type> <a> <op> <b>`, e.g. `u8 0 - 1`")?,
+        }
+    }
+    Ok(())
+}
+
+fn print_outcome(
+    out: &mut Console,
+    ty: &str,
+    a: &str,
+    op: Op,
+    b: &str,
+    outcome: &Outcome,
+) -> io::Result<()> {
+    let method = op.method();
+    writeln!(out, "{a}_{ty} {} {b}_{ty}", op.symbol())?;
+    writeln!(out, "  wrapping_{method:<4}    -> {}", outcome.wrapping)?;
+    writeln!(out, "  checked_{method:<4}     -> {}", outcome.checked)?;
+    writeln!(out, "  saturating_{method:<4}  -> {}", outcome.saturating)?;
+    writeln!(out, "  overflowing_{method:<4} -> {}", outcome.overflowing)?;
+    if outcome.divide_by_zero {
+        let message = match op {
+            Op::Div => "attempt to divide by zero",
+            _ => "attempt to calculate the remainder with a divisor of zero",
+        };
+        writeln!(out, "  Debug and release builds both panic: \"{message}\"")?;
+    } else if outcome.overflowed && matches!(op, Op::Div | Op::Rem) {
+        writeln!(
+            out,
+            "  Debug and release builds both panic: \"attempt to {} with overflow\"",
+            op.verb()
+        )?;
+    } else if outcome.overflowed {
+        writeln!(
+            out,
+            "  Debug build panics: \"attempt to {} with overflow\"",
+            op.verb()
+        )?;
+        writeln!(
+            out,
+            "  Release build wraps around to {}, just like wrapping_{method}",
+            outcome.wrapping
+        )?;
+    } else {
+        writeln!(
+            out,
+            "  No overflow, the plain `{}` operator gives {} in every build",
+            op.symbol(),
+            outcome.wrapping
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(ty: &str, a: &str, op: Op, b: &str) -> Outcome {
+        evaluate(ty, a, op, b).unwrap()
+    }
+
+    #[test]
+    fn addition_past_max_wraps_to_min() {
+        let result = outcome("i8", "127", Op::Add, "1");
+        assert_eq!(result.wrapping, "-128");
+        assert_eq!(result.checked, "None");
+        assert_eq!(result.saturating, "127");
+        assert_eq!(result.overflowing, "(-128, true)");
+        assert!(result.overflowed);
+    }
+
+    #[test]
+    fn unsigned_subtraction_below_zero() {
+        let result = outcome("u8", "0", Op::Sub, "1");
+        assert_eq!(result.wrapping, "255");
+        assert_eq!(result.checked, "None");
+        assert_eq!(result.saturating, "0");
+        assert_eq!(result.overflowing, "(255, true)");
+    }
+
+    #[test]
+    fn multiplication_overflow() {
+        let result = outcome("u8", "200", Op::Mul, "2");
+        assert_eq!(result.wrapping, "144");
+        assert_eq!(result.saturating, "255");
+        assert_eq!(result.overflowing, "(144, true)");
+
+        let result = outcome("i16", "-300", Op::Mul, "200");
+        assert_eq!(result.saturating, "-32768");
+        assert!(result.overflowed);
+    }
+
+    #[test]
+    fn min_divided_by_minus_one_overflows() {
+        let result = outcome("i32", "-2147483648", Op::Div, "-1");
+        assert_eq!(result.wrapping, "-2147483648");
+        assert_eq!(result.checked, "None");
+        assert_eq!(result.saturating, "2147483647");
+        assert_eq!(result.overflowing, "(-2147483648, true)");
+
+        let result = outcome("i64", "-9223372036854775808", Op::Rem, "-1");
+        assert_eq!(result.wrapping, "0");
+        assert_eq!(result.checked, "None");
+        assert_eq!(result.saturating, "-");
+        assert_eq!(result.overflowing, "(0, true)");
+    }
+
+    #[test]
+    fn division_by_zero_only_checked_survives() {
+        for op in [Op::Div, Op::Rem] {
+            let result = outcome("u32", "7", op, "0");
+            assert!(result.divide_by_zero);
+            assert_eq!(result.checked, "None");
+            assert_eq!(result.wrapping, PANICS);
+            assert_eq!(result.saturating, if op == Op::Div { PANICS } else { "-" });
+            assert_eq!(result.overflowing, PANICS);
+        }
+    }
+
+    #[test]
+    fn without_overflow_all_variants_agree() {
+        let result = outcome("i32", "-5", Op::Div, "3");
+        assert_eq!(result.wrapping, "-1");
+        assert_eq!(result.checked, "Some(-1)");
+        assert_eq!(result.saturating, "-1");
+        assert_eq!(result.overflowing, "(-1, false)");
+        assert!(!result.overflowed);
+
+        let result = outcome("usize", "5", Op::Rem, "4");
+        assert_eq!(result.checked, "Some(1)");
+    }
+
+    #[test]
+    fn invalid_input_is_reported() {
+        assert!(
+            evaluate("i8", "128", Op::Add, "1")
+                .unwrap_err()
+                .contains("not a valid i8")
+        );
+        assert!(evaluate("u8", "-1", Op::Add, "1").is_err());
+        assert!(
+            evaluate("f32", "1", Op::Add, "1")
+                .unwrap_err()
+                .contains("not an integer type")
+        );
+        assert_eq!(Op::parse("^"), None);
+    }
+}
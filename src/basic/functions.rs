@@ -1,3 +1,7 @@
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::quiz::{Answer, Question};
 
 /*
     Functions
@@ -12,19 +16,41 @@ Note: If you add a semicolon to the end of an expression,
 
 
 */
-pub fn functions(){
-    println!("-------------------Functions----------------");
+pub fn functions(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "-------------------Functions----------------")?;
     let mut  x = 1;
-    another_function(x);
+    another_function(out, x)?;
     x = return_function(x);
-    println!("The value of x is: {}", x);
+    writeln!(out, "The value of x is: {}", x)?;
+    Ok(())
 }
-fn another_function(x:i32){
-    println!("Value of x is {}", x);
+fn another_function(out: &mut Console, x:i32) -> io::Result<()> {
+    writeln!(out, "Value of x is {}", x)
 }
 /*
 Functions can return values to the code that calls them. We don’t name return values, but we must declare their type after an arrow (->).
 */
 fn return_function(x:i32)->i32{
     x+5
-}
\ No newline at end of file
+}
+
+// `hello_cargo quiz basic` asks these, see quiz.rs
+pub const QUIZ: &[Question] = &[
+    Question {
+        lesson: "basic::functions",
+        function: "return_function()",
+        prompt: "`fn return_function(x:i32)->i32{ x+5 }` What does `return_function(1)` return?",
+        answer: Answer::Output("6"),
+        explanation: "The last expression of the body, without a semicolon, is the return value.",
+    },
+    Question {
+        lesson: "basic::functions",
+        function: "return_function()",
+        prompt: "What happens with `fn plus_one(x: i32) -> i32 { x + 1; }`?",
+        answer: Answer::Choice(
+            &["It returns x + 1", "It returns 0", "It does not compile, the body returns ()"],
+            2,
+        ),
+        explanation: "The semicolon turns `x + 1` into a statement, so the body has no value and returns ().",
+    },
+];
@@ -1,4 +1,8 @@
+// Lessons bind many values only to show the syntax, so they are never read
+#![allow(unused_variables, unused_assignments)]
+
 pub mod variables;
 pub mod datatypes;
 pub mod functions;
-pub mod control_flow;
\ No newline at end of file
+pub mod control_flow;
+pub mod calculator;
@@ -1,25 +1,52 @@
 // By default, variables are immutable. This is one of many nudges Rust gives you to write your code in a way that takes advantage of the safety and easy concurrency that Rust offers.
-pub fn variable() {
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::quiz::{Answer, Question};
+
+pub fn variable(out: &mut Console) -> io::Result<()> {
     let x: i8 = 5; // Immutable by default
-    println!("Value of x {x}");
+    writeln!(out, "Value of x {x}")?;
     // x = 6; // Generate error
-    println!("Value of x {x}");
+    writeln!(out, "Value of x {x}")?;
 
-    println!("----------Mutable-----------");
+    writeln!(out, "----------Mutable-----------")?;
     // Example with mutable
     let mut y = 4;
-    println!("Value of y {y}");
+    writeln!(out, "Value of y {y}")?;
     y = 5;
-    println!("Value of y {y}");
+    writeln!(out, "Value of y {y}")?;
 
-    println!("----------Constant ----------");
+    writeln!(out, "----------Constant ----------")?;
     // Always immutable and declare with const key word
     const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
-    println!("Three Hours in seconds:: {} secs", THREE_HOURS_IN_SECONDS);
+    writeln!(out, "Three Hours in seconds:: {} secs", THREE_HOURS_IN_SECONDS)?;
 
-    println!("---------Shadowing-----------");
+    writeln!(out, "---------Shadowing-----------")?;
     let spaces = "   ";
     let spaces = spaces.len();
-    println!("{} spaces", spaces);
-
+    writeln!(out, "{} spaces", spaces)?;
+    Ok(())
 }
+
+// `hello_cargo quiz basic` asks these, see quiz.rs
+pub const QUIZ: &[Question] = &[
+    Question {
+        lesson: "basic::variables",
+        function: "variable()",
+        prompt: "`let x: i8 = 5;` is followed by `x = 6;`. What happens?",
+        answer: Answer::Choice(
+            &["x becomes 6", "It does not compile, x is immutable", "A new x shadows the old one"],
+            1,
+        ),
+        explanation: "Bindings are immutable by default, assigning twice is error E0384. Write `let mut x` to allow it, or `let x = 6;` to shadow.",
+    },
+    Question {
+        lesson: "basic::variables",
+        function: "variable()",
+        prompt: "`let spaces = \"   \"; let spaces = spaces.len();` What does `println!(\"{} spaces\", spaces)` print?",
+        answer: Answer::Output("3 spaces"),
+        explanation: "The second let shadows the &str with a new variable holding its length, shadowing may change the type.",
+    },
+];
@@ -0,0 +1,364 @@
+// Command line interface
+/*
+Picks what to run from the arguments instead of editing main():
+
+    hello_cargo list
+    hello_cargo run basic::variables
+    hello_cargo run ownership --all
+    hello_cargo gallery E0499
+    hello_cargo shell
+
+Unknown commands and lesson names are reported on stderr with a non-zero exit code.
+*/
+
+use std::process::ExitCode;
+
+use crate::console::Console;
+use crate::exercises::{self, EXERCISES};
+use crate::explain;
+use crate::gallery::{self, SNIPPETS};
+use crate::lessons::{self, CHAPTERS, LESSONS, Lesson};
+use crate::progress::{self, Progress};
+use crate::quiz;
+use crate::shell;
+use crate::snapshots::{self, Outcome};
+
+const USAGE: &str = "Usage: hello_cargo <command>
+
+Commands:
+  list                    List every chapter and lesson in the handbook
+  info <lesson>           Show the summary and prerequisites of a lesson
+  run <lesson>            Run one lesson, e.g. `run basic::variables`
+  run <chapter> --all     Run every lesson of a chapter, e.g. `run ownership --all`
+  run <lesson> --seed <n> Run with a fixed random seed, e.g. `run guessing_game --seed 7`
+  export <lesson> [--all]  Print a lesson, or a whole chapter, as Markdown with its real output
+  snapshots [--update-snapshots]
+                          Compare every lesson's output with its golden snapshot, or rewrite them
+  gallery [<snippet>|--all]
+                          Compile the failing examples with rustc and explain the errors
+  explain <code|message>  Explain a compiler error offline, e.g. `explain E0499` or `explain used after move`
+  exercise [start|check|reset <exercise>]
+                          List the exercises, start one in the workspace, or check your solution
+  quiz <chapter>          Answer the quiz questions of a chapter, e.g. `quiz ownership`
+  progress                Show how much of each chapter you have completed
+  shell                   Browse the handbook from an interactive menu
+  help                    Show this message";
+
+pub fn run(args: &[String]) -> ExitCode {
+    match dispatch(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn dispatch(args: &[String]) -> Result<(), String> {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+    match command.as_str() {
+        "list" => list(),
+        "info" => info(rest),
+        "run" => run_lessons(rest),
+        "export" => export(rest),
+        "snapshots" => snapshots(rest),
+        "gallery" => gallery(rest),
+        "explain" if !rest.is_empty() => {
+            explain::explain(&mut Console::stdout(), &rest.join(" ")).map_err(|err| err.to_string())
+        }
+        "explain" => Err("`explain` expects an error code or part of an error message".to_string()),
+        "exercise" | "exercises" => exercise(rest),
+        "quiz" => quiz(rest),
+        "progress" => show_progress(rest),
+        "shell" => shell::shell(&mut Console::stdout()).map_err(|err| err.to_string()),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        other => Err(format!("unknown command `{other}`\n\n{USAGE}")),
+    }
+}
+
+fn list() -> Result<(), String> {
+    for chapter in CHAPTERS {
+        println!("{}. {}", chapter.number, chapter.title);
+        for lesson in lessons::chapter(chapter.id) {
+            println!("  {:<5} {:<28} {}", lesson.number, lesson.title, lesson.id);
+        }
+    }
+    Ok(())
+}
+
+fn info(args: &[String]) -> Result<(), String> {
+    let [name] = args else {
+        return Err("`info` expects exactly one lesson name".to_string());
+    };
+    let lesson = lessons::find(name)
+        .ok_or_else(|| format!("unknown lesson `{name}`, see `list` for the available lessons"))?;
+    println!("{} {}", lesson.number, lesson.title);
+    println!("  id:            {}", lesson.id);
+    println!("  summary:       {}", lesson.summary);
+    if lesson.prerequisites.is_empty() {
+        println!("  prerequisites: none");
+    } else {
+        println!("  prerequisites: {}", lesson.prerequisites.join(", "));
+    }
+    Ok(())
+}
+
+fn run_lessons(args: &[String]) -> Result<(), String> {
+    // `--seed <n>` makes lessons that use random numbers repeatable
+    let mut args = args.to_vec();
+    let mut seed = None;
+    if let Some(index) = args.iter().position(|arg| arg == "--seed") {
+        let value = args.get(index + 1).ok_or("`--seed` expects a number")?;
+        let value = value
+            .parse::<u64>()
+            .map_err(|err| format!("`{value}` is not a valid seed: {err}"))?;
+        seed = Some(value);
+        args.drain(index..index + 2);
+    }
+
+    for lesson in select_lessons("run", &args)? {
+        let mut console = match seed {
+            Some(seed) => Console::stdout().with_seed(seed),
+            None => Console::stdout(),
+        };
+        (lesson.run)(&mut console).map_err(|err| format!("{}: {err}", lesson.id))?;
+        progress::lesson_run(lesson.id);
+    }
+    Ok(())
+}
+
+fn export(args: &[String]) -> Result<(), String> {
+    for lesson in select_lessons("export", args)? {
+        let mut console = Console::buffered();
+        (lesson.run)(&mut console).map_err(|err| format!("{}: {err}", lesson.id))?;
+        println!("## {} {}\n", lesson.number, lesson.title);
+        println!("{}\n", lesson.summary);
+        println!("```text\n{}```\n", console.into_output());
+    }
+    Ok(())
+}
+
+fn snapshots(args: &[String]) -> Result<(), String> {
+    let update = match args {
+        [] => false,
+        [flag] if flag == "--update-snapshots" => true,
+        _ => return Err("`snapshots` only accepts `--update-snapshots`".to_string()),
+    };
+
+    let mut failed = 0;
+    for lesson in LESSONS {
+        let outcome =
+            snapshots::check(lesson, update).map_err(|err| format!("{}: {err}", lesson.id))?;
+        match outcome {
+            Outcome::Matched => println!("ok       {}", lesson.id),
+            Outcome::Updated => println!("updated  {}", lesson.id),
+            Outcome::Skipped => println!(
+                "skipped  {} (its snapshot is taken on a 64-bit target)",
+                lesson.id
+            ),
+            Outcome::Missing => {
+                failed += 1;
+                println!(
+                    "missing  {} ({})",
+                    lesson.id,
+                    snapshots::snapshot_path(lesson).display()
+                );
+            }
+            Outcome::Changed {
+                line,
+                expected,
+                actual,
+            } => {
+                failed += 1;
+                println!("changed  {} at line {line}", lesson.id);
+                println!("  - {expected}");
+                println!("  + {actual}");
+            }
+            Outcome::TrailingNewline { missing } => {
+                failed += 1;
+                println!(
+                    "changed  {}: {}",
+                    lesson.id,
+                    snapshots::trailing_newline(missing)
+                );
+            }
+            Outcome::LineEndings => {
+                failed += 1;
+                println!("changed  {}: {}", lesson.id, snapshots::LINE_ENDINGS);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "{failed} lesson(s) differ from their snapshots, rerun with --update-snapshots if this is intended"
+        ));
+    }
+    Ok(())
+}
+
+fn gallery(args: &[String]) -> Result<(), String> {
+    let selected: Vec<&gallery::Snippet> = match args {
+        [] => {
+            for snippet in SNIPPETS {
+                println!(
+                    "  {:<20} {}  {}",
+                    snippet.id, snippet.expected, snippet.title
+                );
+            }
+            println!("\nRun `gallery <snippet>` to compile one, or `gallery --all`.");
+            return Ok(());
+        }
+        [flag] if flag == "--all" => SNIPPETS.iter().collect(),
+        [name] => vec![gallery::find(name).ok_or_else(|| {
+            format!("unknown snippet `{name}`, run `gallery` for the available snippets")
+        })?],
+        _ => return Err("`gallery` expects one snippet name or `--all`".to_string()),
+    };
+
+    let mut console = Console::stdout();
+    let mut unexpected = Vec::new();
+    for snippet in selected {
+        if !gallery::show(&mut console, snippet).map_err(|err| format!("{}: {err}", snippet.id))? {
+            unexpected.push(snippet.id);
+        }
+    }
+    if !unexpected.is_empty() {
+        return Err(format!(
+            "rustc did not report the expected error for {}",
+            unexpected.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+fn exercise(args: &[String]) -> Result<(), String> {
+    let (action, id) = match args {
+        [] => {
+            for exercise in EXERCISES {
+                let started = if exercises::path(exercise).exists() {
+                    "started"
+                } else {
+                    ""
+                };
+                println!("  {:<16} {:<36} {started}", exercise.id, exercise.title);
+            }
+            println!(
+                "\nRun `exercise start <exercise>`, the files go to {}",
+                exercises::workspace().display()
+            );
+            return Ok(());
+        }
+        [action, id] => (action.as_str(), id),
+        _ => {
+            return Err(
+                "`exercise` expects `start`, `check` or `reset` and an exercise".to_string(),
+            );
+        }
+    };
+    let exercise = exercises::find(id).ok_or_else(|| {
+        format!("unknown exercise `{id}`, run `exercise` for the available exercises")
+    })?;
+    let path = exercises::path(exercise);
+
+    match action {
+        "start" | "reset" => {
+            let copied = exercises::start(exercise, action == "reset")
+                .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+            if !copied {
+                println!("You already started this exercise, `exercise reset {id}` starts over.");
+            }
+            println!("{}\n", exercise.stub.trim_end());
+            println!("Edit {} and run `exercise check {id}`.", path.display());
+            Ok(())
+        }
+        "check" => {
+            if !path.exists() {
+                return Err(format!(
+                    "{} does not exist, run `exercise start {id}` first",
+                    path.display()
+                ));
+            }
+            let outcome = exercises::check(exercise).map_err(|err| format!("{id}: {err}"))?;
+            exercises::report(&mut Console::stdout(), exercise, &outcome)
+                .map_err(|err| err.to_string())?;
+            match outcome {
+                exercises::Outcome::Passed { .. } => {
+                    progress::exercise_solved(exercise.id);
+                    Ok(())
+                }
+                _ => Err(format!("{id} is not solved yet")),
+            }
+        }
+        other => Err(format!(
+            "unknown action `{other}`, use `start`, `check` or `reset`"
+        )),
+    }
+}
+
+fn quiz(args: &[String]) -> Result<(), String> {
+    let [chapter] = args else {
+        return Err("`quiz` expects exactly one chapter name, e.g. `quiz basic`".to_string());
+    };
+    let chapter = lessons::find_chapter(chapter)
+        .ok_or_else(|| format!("unknown chapter `{chapter}`, see `list` for the chapters"))?;
+    match quiz::quiz(&mut Console::stdout(), chapter.id).map_err(|err| err.to_string())? {
+        Some(score) if score.passed() => {
+            progress::quiz_passed(chapter.id);
+            Ok(())
+        }
+        Some(score) => Err(format!(
+            "{} of {} answers were right, {}% are needed to pass",
+            score.correct,
+            score.total,
+            quiz::PASS_PERCENT
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn show_progress(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        return Err("`progress` does not take arguments".to_string());
+    }
+    let path = progress::path().ok_or(progress::NO_PATH)?;
+    let progress =
+        Progress::load(&path).map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    println!("Progress saved in {}\n", path.display());
+    progress::report(&mut Console::stdout(), &progress).map_err(|err| err.to_string())
+}
+
+// `<lesson>` selects one lesson, `<chapter> --all` every lesson of the chapter
+fn select_lessons(command: &str, args: &[String]) -> Result<Vec<&'static Lesson>, String> {
+    let all = args.iter().any(|arg| arg == "--all");
+    let names: Vec<&String> = args.iter().filter(|arg| *arg != "--all").collect();
+    let [name] = names.as_slice() else {
+        return Err(format!(
+            "`{command}` expects exactly one lesson or chapter name"
+        ));
+    };
+
+    if all {
+        let chapter = lessons::chapter(name);
+        if chapter.is_empty() {
+            return Err(format!("unknown chapter `{name}`"));
+        }
+        return Ok(chapter);
+    }
+
+    match lessons::find(name) {
+        Some(lesson) => Ok(vec![lesson]),
+        None if !lessons::chapter(name).is_empty() => Err(format!(
+            "`{name}` is a chapter, use `{command} {name} --all` to include all of its lessons"
+        )),
+        None => Err(format!(
+            "unknown lesson `{name}`, see `list` for the available lessons"
+        )),
+    }
+}
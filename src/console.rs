@@ -0,0 +1,199 @@
+// Lesson input and output
+/*
+Lessons write through a Console instead of calling println! directly.
+The CLI and the shell hand them a Console that forwards to stdout, while tests and exporters
+hand them a buffered one and read back exactly what a learner would have seen.
+
+Console implements std::io::Write, so lessons use it with writeln!(out, ...).
+Interactive lessons read their answers with read_line(), from stdin or from a script of lines.
+Scripted lines are echoed into the output so a captured transcript reads like a real session.
+Menus read with read_keys() instead: on a terminal it switches to raw mode, so arrow up/down
+come back on the key press, everywhere else it is read_line().
+Lessons that need randomness take their random number generator from the console too:
+a stdout console seeds it from the operating system, a scripted one from a fixed seed,
+so a scripted session plays out the same way every time.
+*/
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::alloc_tracking;
+
+// Seed used by scripted consoles unless with_seed() picks another one
+const SCRIPT_SEED: u64 = 2024;
+
+// What read_keys() returns for the arrow keys, the same escape sequences a terminal in line mode sends
+pub const ARROW_UP: &str = "\x1b[A";
+pub const ARROW_DOWN: &str = "\x1b[B";
+
+pub struct Console {
+    source: Source,
+    sink: Sink,
+    seed: Option<u64>,
+}
+
+enum Source {
+    Stdin(io::Stdin),
+    Script(VecDeque<String>),
+}
+
+enum Sink {
+    Stdout(io::Stdout),
+    Buffer(Vec<u8>),
+}
+
+impl Console {
+    // Reads from stdin and writes straight to the terminal
+    pub fn stdout() -> Self {
+        Console {
+            source: Source::Stdin(io::stdin()),
+            sink: Sink::Stdout(io::stdout()),
+            seed: None,
+        }
+    }
+
+    // Keeps everything written in memory, read it back with into_output()
+    // There is no input, so read_line() reports the end of input straight away
+    pub fn buffered() -> Self {
+        Console::scripted(&[])
+    }
+
+    // Buffered console that answers read_line() with the given lines, one per call
+    pub fn scripted(lines: &[&str]) -> Self {
+        Console {
+            source: Source::Script(lines.iter().map(|line| line.to_string()).collect()),
+            sink: Sink::Buffer(Vec::new()),
+            seed: Some(SCRIPT_SEED),
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // A new random number generator, seeded as described at the top of this file
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        }
+    }
+
+    // The next line of input without its line ending, None once the input is exhausted
+    pub fn read_line(&mut self) -> io::Result<Option<String>> {
+        self.flush()?;
+        alloc_tracking::untracked(|| self.next_line())
+    }
+
+    // Like read_line(), but on a terminal arrow up/down return ARROW_UP/ARROW_DOWN as soon as they are
+    // pressed on an empty line, without waiting for Enter
+    pub fn read_keys(&mut self) -> io::Result<Option<String>> {
+        let Source::Stdin(stdin) = &self.source else {
+            return self.read_line();
+        };
+        if !stdin.is_terminal() || !matches!(self.sink, Sink::Stdout(_)) {
+            return self.read_line();
+        }
+        self.flush()?;
+        terminal::enable_raw_mode()?;
+        let keys = alloc_tracking::untracked(|| self.next_keys());
+        terminal::disable_raw_mode()?;
+        keys
+    }
+
+    // Raw mode does not echo what is typed and a newline does not return the carriage,
+    // so both are written by hand
+    fn next_keys(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        loop {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            let control = key.modifiers.contains(KeyModifiers::CONTROL);
+            match key.code {
+                KeyCode::Up | KeyCode::Down if line.is_empty() => {
+                    write!(self, "\r\n")?;
+                    let arrow = if key.code == KeyCode::Up {
+                        ARROW_UP
+                    } else {
+                        ARROW_DOWN
+                    };
+                    return Ok(Some(arrow.to_string()));
+                }
+                KeyCode::Enter => {
+                    write!(self, "\r\n")?;
+                    return Ok(Some(line));
+                }
+                // Ctrl-C and Ctrl-D end the input like they do in line mode
+                KeyCode::Char('c' | 'd') if control => {
+                    write!(self, "\r\n")?;
+                    return Ok(None);
+                }
+                KeyCode::Char(c) if !control => {
+                    line.push(c);
+                    write!(self, "{c}")?;
+                }
+                KeyCode::Backspace if !line.is_empty() => {
+                    line.pop();
+                    write!(self, "\x08 \x08")?;
+                }
+                _ => {}
+            }
+            self.flush()?;
+        }
+    }
+
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        match &mut self.source {
+            Source::Stdin(stdin) => {
+                let mut line = String::new();
+                if stdin.lock().read_line(&mut line)? == 0 {
+                    return Ok(None);
+                }
+                Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+            }
+            Source::Script(lines) => {
+                let Some(line) = lines.pop_front() else {
+                    return Ok(None);
+                };
+                writeln!(self, "{line}")?;
+                Ok(Some(line))
+            }
+        }
+    }
+
+    // The captured text, always empty for a stdout console
+    pub fn into_output(self) -> String {
+        match self.sink {
+            Sink::Stdout(_) => String::new(),
+            Sink::Buffer(buffer) => String::from_utf8(buffer)
+                .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned()),
+        }
+    }
+}
+
+impl Write for Console {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Growing the buffer is not part of what a lesson allocates
+        alloc_tracking::untracked(|| match &mut self.sink {
+            Sink::Stdout(stdout) => stdout.write(buf),
+            Sink::Buffer(buffer) => buffer.write(buf),
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Stdout(stdout) => stdout.flush(),
+            Sink::Buffer(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+// Exercises
+/*
+Reading code is not the same as writing it, so some lessons come with an exercise.
+Each exercise under exercises/<id>/ has three files:
+
+    stub.rs       what the learner starts from: a signature with todo!() and a description
+    tests.rs      the tests that decide whether the exercise is solved, hidden from the learner
+    solution.rs   a working answer, only read by `cargo test` to make sure the tests can be passed
+
+    hello_cargo exercise                   list the exercises
+    hello_cargo exercise start first_word  copy the stub into the workspace and show the task
+    hello_cargo exercise check first_word  compile the learner's file with the hidden tests and run them
+    hello_cargo exercise reset first_word  start over from the stub
+
+The workspace is the hello_cargo_exercises directory in the current directory,
+or the directory named by the HELLO_CARGO_EXERCISES environment variable.
+
+check appends the hidden tests to the learner's file as a `mod hidden_tests`, compiles it with
+`rustc --test` (the same rustc as the gallery, see gallery.rs) and runs the test binary.
+A solution that never finishes is stopped after TIMEOUT.
+An exercise only counts as solved when the test binary exits successfully and every `#[test]` in tests.rs
+reported ok, so a solution that overflows the stack, aborts or exits early is not mistaken for a pass.
+*/
+
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::console::Console;
+use crate::gallery;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Exercise {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub lesson: &'static str,
+    pub stub: &'static str,
+    pub tests: &'static str,
+    pub hints: &'static [&'static str],
+}
+
+pub const EXERCISES: &[Exercise] = &[
+    Exercise {
+        id: "first_word",
+        title: "Implement first_word for &str",
+        lesson: "ownership::slice_type",
+        stub: include_str!("../exercises/first_word/stub.rs"),
+        tests: include_str!("../exercises/first_word/tests.rs"),
+        hints: &[
+            "trim_start() returns a slice of the text without the whitespace in front.",
+            "find(char::is_whitespace) gives the byte index of the first whitespace, if there is one.",
+            "Return &s[..end]: a slice borrows from s, so no String has to be built.",
+        ],
+    },
+    Exercise {
+        id: "return_tuple",
+        title: "Give ownership back with a tuple",
+        lesson: "ownership::intro_to_ownership",
+        stub: include_str!("../exercises/return_tuple/stub.rs"),
+        tests: include_str!("../exercises/return_tuple/tests.rs"),
+        hints: &[
+            "Read the length with some_string.len() before the String is moved into the tuple.",
+            "(len, some_string) moves the String back to the caller, clone() is not needed.",
+        ],
+    },
+    Exercise {
+        id: "change_string",
+        title: "Change a String through &mut",
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        stub: include_str!("../exercises/change_string/stub.rs"),
+        tests: include_str!("../exercises/change_string/tests.rs"),
+        hints: &[
+            "push_str() appends a string slice to a String.",
+            "some_string is a mutable reference, so some_string.push_str(...) changes the caller's String.",
+        ],
+    },
+];
+
+pub enum Outcome {
+    Passed {
+        tests: usize,
+    },
+    Failed {
+        passed: usize,
+        failures: Vec<(String, String)>,
+    },
+    CompileError(String),
+    TimedOut,
+    // The test binary stopped before every test reported a result
+    Crashed {
+        status: String,
+        passed: usize,
+        missing: Vec<String>,
+    },
+}
+
+pub fn find(id: &str) -> Option<&'static Exercise> {
+    EXERCISES.iter().find(|exercise| exercise.id == id)
+}
+
+pub fn workspace() -> PathBuf {
+    env::var_os("HELLO_CARGO_EXERCISES")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("hello_cargo_exercises"))
+}
+
+pub fn path(exercise: &Exercise) -> PathBuf {
+    workspace().join(format!("{}.rs", exercise.id))
+}
+
+// Copies the stub into the workspace, unless the learner already started; true when it was copied
+pub fn start(exercise: &Exercise, reset: bool) -> io::Result<bool> {
+    let path = path(exercise);
+    if path.exists() && !reset {
+        return Ok(false);
+    }
+    fs::create_dir_all(workspace())?;
+    fs::write(&path, exercise.stub)?;
+    Ok(true)
+}
+
+// Checks the learner's file in the workspace
+pub fn check(exercise: &Exercise) -> io::Result<Outcome> {
+    let source = fs::read_to_string(path(exercise))?;
+    check_source(exercise, &source)
+}
+
+// Compiles the source together with the hidden tests and runs them
+pub fn check_source(exercise: &Exercise, source: &str) -> io::Result<Outcome> {
+    // Numbered, so two checks of the same exercise running side by side never share files
+    static CHECKS: AtomicUsize = AtomicUsize::new(0);
+    let dir = env::temp_dir().join(format!(
+        "hello_cargo-exercise-{}-{}-{}",
+        process::id(),
+        exercise.id,
+        CHECKS.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir)?;
+    let outcome = compile_and_run(exercise, source, &dir);
+    // The scratch files are of no use once the tests have run, whatever the outcome
+    let _ = fs::remove_dir_all(&dir);
+    outcome
+}
+
+fn compile_and_run(exercise: &Exercise, source: &str, dir: &Path) -> io::Result<Outcome> {
+    let file = format!("{}.rs", exercise.id);
+    let program = format!(
+        "{source}\n\n#[cfg(test)]\nmod hidden_tests {{\n    use super::*;\n\n{}\n}}\n",
+        exercise.tests
+    );
+    fs::write(dir.join(&file), program)?;
+
+    let binary = format!("{}{}", exercise.id, env::consts::EXE_SUFFIX);
+    let output = gallery::rustc()
+        .current_dir(dir)
+        .args(["--edition", "2024", "--test", "--cap-lints", "allow"])
+        .args(["--color", "never", "-o", &binary, &file])
+        .output()?;
+    if !output.status.success() {
+        return Ok(Outcome::CompileError(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+
+    let mut child = process::Command::new(dir.join(&binary))
+        .args(["--test-threads", "1", "--color", "never"])
+        .env("RUST_BACKTRACE", "0")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Read the report while the tests run, a full pipe would stop a solution that prints a lot
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut report = String::new();
+        stdout.read_to_string(&mut report).map(|_| report)
+    });
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > TIMEOUT {
+            child.kill()?;
+            child.wait()?;
+            return Ok(Outcome::TimedOut);
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    let report = reader.join().expect("the report reader does not panic")?;
+    Ok(parse_results(&report, status, &test_names(exercise.tests)))
+}
+
+// The names of the `#[test]` functions in tests.rs
+fn test_names(tests: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut lines = tests.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() != "#[test]" {
+            continue;
+        }
+        let name = lines
+            .find_map(|line| line.trim().strip_prefix("fn "))
+            .and_then(|rest| rest.split('(').next());
+        names.extend(name);
+    }
+    names
+}
+
+// Reads libtest's report: `test hidden_tests::name ... ok` lines, and the panic of each failed test
+// below `---- hidden_tests::name stdout ----`
+fn parse_results(report: &str, status: ExitStatus, tests: &[&str]) -> Outcome {
+    let mut passed = Vec::new();
+    let mut failed = Vec::new();
+    for line in report.lines() {
+        let Some(rest) = line.strip_prefix("test ") else {
+            continue;
+        };
+        if let Some(name) = rest.strip_suffix(" ... ok") {
+            passed.push(test_name(name));
+        } else if let Some(name) = rest.strip_suffix(" ... FAILED") {
+            failed.push(test_name(name).to_string());
+        }
+    }
+    if failed.is_empty() {
+        let missing: Vec<String> = tests
+            .iter()
+            .filter(|test| !passed.contains(test))
+            .map(|test| test.to_string())
+            .collect();
+        if status.success() && missing.is_empty() && !passed.is_empty() {
+            return Outcome::Passed {
+                tests: passed.len(),
+            };
+        }
+        return Outcome::Crashed {
+            status: status.to_string(),
+            passed: passed.len(),
+            missing,
+        };
+    }
+    let passed = passed.len();
+
+    let failures = failed
+        .into_iter()
+        .map(|name| {
+            let header = format!("---- hidden_tests::{name} stdout ----");
+            // The `thread '...' panicked at` line points into the scratch file, the message is what helps
+            let message: Vec<&str> = report
+                .lines()
+                .skip_while(|line| *line != header)
+                .skip(1)
+                .skip_while(|line| line.is_empty())
+                .take_while(|line| !line.is_empty())
+                .filter(|line| !line.starts_with("thread '") && !line.starts_with("note: "))
+                .collect();
+            (name, message.join("\n"))
+        })
+        .collect();
+    Outcome::Failed { passed, failures }
+}
+
+fn test_name(path: &str) -> &str {
+    path.strip_prefix("hidden_tests::").unwrap_or(path)
+}
+
+pub fn report(out: &mut Console, exercise: &Exercise, outcome: &Outcome) -> io::Result<()> {
+    match outcome {
+        Outcome::Passed { tests } => {
+            writeln!(out, "All {tests} tests pass, {} is solved!", exercise.id)?;
+            return Ok(());
+        }
+        Outcome::Failed { passed, failures } => {
+            writeln!(out, "{passed} of {} tests pass.", passed + failures.len())?;
+            for (name, message) in failures {
+                writeln!(out, "FAILED {name}")?;
+                for line in message.lines() {
+                    writeln!(out, "    {line}")?;
+                }
+            }
+        }
+        Outcome::CompileError(message) => {
+            writeln!(out, "The file does not compile yet, rustc says:")?;
+            writeln!(out, "{}", message.trim_end())?;
+        }
+        Outcome::Crashed {
+            status,
+            passed,
+            missing,
+        } => {
+            writeln!(
+                out,
+                "The tests stopped early ({status}) after {passed} passed, \
+                 did the solution overflow the stack, abort or exit?"
+            )?;
+            if !missing.is_empty() {
+                writeln!(out, "No result for: {}", missing.join(", "))?;
+            }
+        }
+        Outcome::TimedOut => writeln!(
+            out,
+            "The tests did not finish within {} seconds, is there a loop that never ends?",
+            TIMEOUT.as_secs()
+        )?,
+    }
+    writeln!(out, "Hints:")?;
+    for hint in exercise.hints {
+        writeln!(out, "  - {hint}")?;
+    }
+    writeln!(out, "The lesson: hello_cargo run {}", exercise.lesson)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons;
+
+    #[test]
+    fn solutions_pass_and_stubs_do_not() {
+        for exercise in EXERCISES {
+            assert!(lessons::find(exercise.lesson).is_some(), "{}", exercise.id);
+            let solution = fs::read_to_string(
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("exercises")
+                    .join(exercise.id)
+                    .join("solution.rs"),
+            )
+            .unwrap();
+            match check_source(exercise, &solution).unwrap() {
+                Outcome::Passed { tests } => {
+                    assert_eq!(tests, test_names(exercise.tests).len(), "{}", exercise.id)
+                }
+                _ => panic!("the solution of {} does not pass", exercise.id),
+            }
+            match check_source(exercise, exercise.stub).unwrap() {
+                Outcome::Failed { passed: 0, .. } => {}
+                _ => panic!(
+                    "the stub of {} should compile and fail every test",
+                    exercise.id
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn exiting_early_is_not_a_pass() {
+        let exercise = find("first_word").unwrap();
+        let source = "pub fn first_word(_s: &str) -> &str { std::process::exit(0) }";
+        match check_source(exercise, source).unwrap() {
+            Outcome::Crashed {
+                passed: 0, missing, ..
+            } => assert_eq!(missing.len(), test_names(exercise.tests).len()),
+            _ => panic!("a solution that exits should not pass"),
+        }
+    }
+
+    #[test]
+    fn compile_errors_are_reported() {
+        let exercise = find("change_string").unwrap();
+        let source = "pub fn change(some_string: &String) { some_string.push_str(\", world\"); }";
+        match check_source(exercise, source).unwrap() {
+            Outcome::CompileError(message) => assert!(message.contains("E0"), "{message}"),
+            _ => panic!("borrowing immutably should not compile"),
+        }
+    }
+}
@@ -0,0 +1,345 @@
+// Error code explainer
+/*
+Lessons mention compiler errors by their text, `cannot borrow `s` as mutable more than once at a time [E0499]`
+or `value used after being moved`. `hello_cargo explain` looks such an error up without going online:
+
+    hello_cargo explain E0499              the explanation of an error code
+    hello_cargo explain used after move    search the error messages the handbook knows about
+
+The explanation comes from `rustc --explain`, which ships with every toolchain.
+When rustc cannot be run, a short explanation bundled with the handbook is shown instead.
+Either way it ends with the lesson that demonstrates the error.
+
+The search is forgiving: every word of the query is compared with the words of the known messages,
+and the start of a word or a word that is one typo away still counts, so `borow mutable twice` finds E0499.
+*/
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::gallery;
+use crate::lessons;
+
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub messages: &'static [&'static str],
+    pub lesson: &'static str,
+    pub function: &'static str,
+    pub fallback: &'static str,
+}
+
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E0106",
+        messages: &[
+            "missing lifetime specifier",
+            "this function's return type contains a borrowed value, but there is no value for it to be borrowed from",
+        ],
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        function: "dangle()",
+        fallback: "A reference in a function signature needs a lifetime that says what it borrows from.
+A function without reference parameters, like dangle(), has nothing its returned reference could borrow,
+so the reference would point to a value that is dropped when the function returns. Return an owned value instead.",
+    },
+    ErrorCode {
+        code: "E0282",
+        messages: &["type annotations needed", "consider giving `guess` an explicit type"],
+        lesson: "basic::datatypes",
+        function: "data_types()",
+        fallback: "The compiler could not infer the type of a value.
+`\"42\".parse()` can produce many types, so the binding needs an annotation such as `let guess: u32 = ...`.",
+    },
+    ErrorCode {
+        code: "E0381",
+        messages: &[
+            "used binding isn't initialized",
+            "binding declared here but left uninitialized",
+        ],
+        lesson: "ownership::intro_to_ownership::borrows",
+        function: "borrows()",
+        fallback: "A variable was used before a value was assigned to it.
+Rust has no null or default values, a variable declared with `let r;` has to be assigned before it is read.",
+    },
+    ErrorCode {
+        code: "E0382",
+        messages: &[
+            "borrow of moved value",
+            "use of moved value",
+            "value used after being moved",
+            "value borrowed here after move",
+        ],
+        lesson: "ownership::intro_to_ownership",
+        function: "intro_to_string()",
+        fallback: "A value was used after its ownership was moved somewhere else.
+Assigning a String to another variable or passing it to a function moves it, the old variable is no longer valid.
+Use the new owner, pass a reference, or clone() the value when a second copy is really needed.",
+    },
+    ErrorCode {
+        code: "E0384",
+        messages: &[
+            "cannot assign twice to immutable variable",
+            "consider making this binding mutable",
+        ],
+        lesson: "basic::variables",
+        function: "variable()",
+        fallback: "A variable that is not declared with `mut` was assigned a second time.
+Variables are immutable by default, declare it with `let mut` or shadow it with a new `let`.",
+    },
+    ErrorCode {
+        code: "E0425",
+        messages: &["cannot find value in this scope", "not found in this scope"],
+        lesson: "ownership::intro_to_ownership",
+        function: "basic_ownership()",
+        fallback: "A name was used where no variable of that name exists.
+A variable is valid from where it is declared until the end of its block, after the closing curly bracket it is gone.",
+    },
+    ErrorCode {
+        code: "E0499",
+        messages: &[
+            "cannot borrow as mutable more than once at a time",
+            "second mutable borrow occurs here",
+            "first mutable borrow occurs here",
+        ],
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        function: "mutable_reference()",
+        fallback: "A value was borrowed mutably twice while the first borrow was still in use.
+At any given time you can have one mutable reference to a value. End the first borrow,
+for example in its own block or by not using it any more, before taking the second one.",
+    },
+    ErrorCode {
+        code: "E0502",
+        messages: &[
+            "cannot borrow as mutable because it is also borrowed as immutable",
+            "cannot borrow as immutable because it is also borrowed as mutable",
+            "immutable borrow occurs here",
+            "mutable borrow occurs here",
+        ],
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        function: "mutable_reference()",
+        fallback: "A value was borrowed mutably while an immutable borrow of it was still in use, or the other way around.
+You can have either one mutable reference or any number of immutable references, not both at once.",
+    },
+    ErrorCode {
+        code: "E0505",
+        messages: &[
+            "cannot move out of because it is borrowed",
+            "move out of occurs here",
+        ],
+        lesson: "ownership::intro_to_ownership::borrows",
+        function: "borrows()",
+        fallback: "A value was moved, for example into drop() or a function, while a reference to it was still in use.
+The reference would point to a value that has a new owner or was dropped. Finish using the reference first.",
+    },
+    ErrorCode {
+        code: "E0506",
+        messages: &[
+            "cannot assign to because it is borrowed",
+            "is assigned to here but it was already borrowed",
+        ],
+        lesson: "ownership::intro_to_ownership::borrows",
+        function: "borrows()",
+        fallback: "A variable was given a new value while a reference to its old value was still in use.
+Assigning drops the old String, so the reference would dangle. Finish using the reference first.",
+    },
+    ErrorCode {
+        code: "E0596",
+        messages: &[
+            "cannot borrow as mutable, as it is not declared as mutable",
+            "cannot borrow as mutable, as it is behind a `&` reference",
+        ],
+        lesson: "ownership::intro_to_ownership::borrows",
+        function: "borrows()",
+        fallback: "Something was borrowed mutably that cannot be changed:
+a variable declared without `mut`, or data reached through an immutable `&` reference.
+Declare the variable with `let mut`, or borrow it with `&mut` from the start.",
+    },
+    ErrorCode {
+        code: "E0597",
+        messages: &[
+            "does not live long enough",
+            "dropped here while still borrowed",
+            "borrowed value does not live long enough",
+        ],
+        lesson: "ownership::intro_to_ownership::borrows",
+        function: "borrows()",
+        fallback: "A reference outlives the value it borrows from.
+The value is dropped at the end of its block while the reference is still used after it. Declare the value in the outer block.",
+    },
+];
+
+// Error codes are matched exactly (`E0499`, `e0499` or just `0499`), anything else is searched for
+pub fn explain(out: &mut Console, query: &str) -> io::Result<()> {
+    let query = query.trim();
+    if let Some(code) = as_error_code(query) {
+        return match ERROR_CODES.iter().find(|entry| entry.code == code) {
+            Some(entry) => print_entry(out, entry),
+            // rustc knows every error code, the handbook only the ones it demonstrates
+            None => match rustc_explain(&code) {
+                Some(text) => writeln!(out, "{}", text.trim_end()),
+                None => writeln!(out, "No explanation for {code} is available offline."),
+            },
+        };
+    }
+
+    let matches = search(query);
+    match matches.as_slice() {
+        [] => writeln!(out, "No known error message matches `{query}`."),
+        [entry] => print_entry(out, entry),
+        [best, ..] => {
+            writeln!(out, "Error messages matching `{query}`, best match first:")?;
+            for entry in &matches {
+                writeln!(out, "  {}  {}", entry.code, entry.messages[0])?;
+            }
+            writeln!(out)?;
+            print_entry(out, best)
+        }
+    }
+}
+
+fn print_entry(out: &mut Console, entry: &ErrorCode) -> io::Result<()> {
+    writeln!(
+        out,
+        "--------- {}: {} ---------",
+        entry.code, entry.messages[0]
+    )?;
+    match rustc_explain(entry.code) {
+        Some(text) => writeln!(out, "{}", text.trim_end())?,
+        None => {
+            writeln!(
+                out,
+                "(rustc is not available, showing the handbook's own explanation)"
+            )?;
+            writeln!(out, "{}", entry.fallback)?;
+        }
+    }
+    writeln!(out)?;
+    if let Some(lesson) = lessons::find(entry.lesson) {
+        writeln!(
+            out,
+            "In the handbook: {} {}, see {} (hello_cargo run {})",
+            lesson.number, lesson.title, entry.function, lesson.id
+        )?;
+    }
+    for snippet in gallery::SNIPPETS
+        .iter()
+        .filter(|snippet| snippet.expected == entry.code)
+    {
+        writeln!(
+            out,
+            "Compile it yourself: hello_cargo gallery {}",
+            snippet.id
+        )?;
+    }
+    Ok(())
+}
+
+// `E0499`, `e0499` and `0499` -> E0499
+fn as_error_code(query: &str) -> Option<String> {
+    let digits = query.strip_prefix(['E', 'e']).unwrap_or(query);
+    (digits.len() == 4 && digits.bytes().all(|byte| byte.is_ascii_digit()))
+        .then(|| format!("E{digits}"))
+}
+
+fn rustc_explain(code: &str) -> Option<String> {
+    let output = gallery::rustc().args(["--explain", code]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Entries with at least one matching word, the ones matching the most words first
+fn search(query: &str) -> Vec<&'static ErrorCode> {
+    let query = words(query);
+    let mut scored: Vec<(usize, &ErrorCode)> = ERROR_CODES
+        .iter()
+        .map(|entry| (score(&query, entry), entry))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    // A stable sort keeps equally good matches in error code order
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+// The number of query words found in the entry's best matching message
+fn score(query: &[String], entry: &ErrorCode) -> usize {
+    entry
+        .messages
+        .iter()
+        .map(|message| {
+            let message = words(message);
+            query
+                .iter()
+                .filter(|word| message.iter().any(|candidate| similar(word, candidate)))
+                .count()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Lowercase words without punctuation, so `s` and `E0499` in backticks or brackets still match
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// Short words must match exactly, longer ones may be the start of a word (`move` and `moved`)
+// or one typo apart (`borow` and `borrow`)
+fn similar(query: &str, word: &str) -> bool {
+    if query == word {
+        return true;
+    }
+    let long_enough = query.chars().count() >= 4;
+    (long_enough && word.starts_with(query))
+        || (long_enough && word.chars().count() >= 5 && edit_distance(query, word) <= 1)
+}
+
+// Levenshtein distance: the fewest inserted, removed or replaced characters that turn a into b
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(ca != *cb);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_are_recognised_in_any_spelling() {
+        assert_eq!(as_error_code("E0499").as_deref(), Some("E0499"));
+        assert_eq!(as_error_code("e0499").as_deref(), Some("E0499"));
+        assert_eq!(as_error_code("0499").as_deref(), Some("E0499"));
+        assert_eq!(as_error_code("E499"), None);
+        assert_eq!(as_error_code("moved"), None);
+    }
+
+    #[test]
+    fn messages_are_found_despite_typos() {
+        assert_eq!(search("value used after being moved")[0].code, "E0382");
+        assert_eq!(
+            search("cannot borrow `s` as mutable more than once at a time")[0].code,
+            "E0499"
+        );
+        assert_eq!(search("borow mutable more then once")[0].code, "E0499");
+        assert_eq!(search("used after move")[0].code, "E0382");
+        assert!(search("segmentation").is_empty());
+    }
+
+    #[test]
+    fn every_entry_points_at_a_lesson() {
+        for entry in ERROR_CODES {
+            assert!(lessons::find(entry.lesson).is_some(), "{}", entry.code);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// Compiler error gallery
+/*
+The ownership lessons keep their failing examples as comments, because a file that does not compile
+stops the whole handbook from building. The gallery keeps those examples as real files under gallery/
+and hands them to the local rustc, so a learner reads the compiler's own message instead of a copy of it:
+
+    hello_cargo gallery                    list the snippets
+    hello_cargo gallery two_mutable_borrows
+    hello_cargo gallery E0499              a snippet can also be picked by its error code
+    hello_cargo gallery --all
+
+Each snippet declares the error code it is expected to fail with. When rustc reports something else,
+the snippet is flagged, which usually means a newer compiler words or numbers the error differently.
+
+The compiler is taken from the RUSTC environment variable when it is set, `rustc` from PATH otherwise.
+*/
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::{self, Command};
+
+use crate::console::Console;
+
+pub struct Snippet {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub code: &'static str,
+    pub expected: &'static str,
+    pub lesson: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const SNIPPETS: &[Snippet] = &[
+    Snippet {
+        id: "out_of_scope",
+        title: "Using a variable outside of its scope",
+        code: include_str!("../gallery/out_of_scope.rs"),
+        expected: "E0425",
+        lesson: "ownership::intro_to_ownership",
+        explanation: "s1 is declared inside the inner block, so its scope ends at the closing curly bracket.
+After that the name does not exist any more and the compiler cannot find it.",
+    },
+    Snippet {
+        id: "use_after_move",
+        title: "Using a String after it was moved",
+        code: include_str!("../gallery/use_after_move.rs"),
+        expected: "E0382",
+        lesson: "ownership::intro_to_ownership",
+        explanation: "`let s2 = s1;` copies the ptr, len and capacity and moves ownership of the heap data to s2.
+s1 is no longer valid, otherwise both would free the same memory when they go out of scope.",
+    },
+    Snippet {
+        id: "moved_into_function",
+        title: "Using a String after passing it to a function",
+        code: include_str!("../gallery/moved_into_function.rs"),
+        expected: "E0382",
+        lesson: "ownership::intro_to_ownership",
+        explanation: "Passing a value to a function moves it just like an assignment does.
+takes_ownership() now owns the String and drops it when it returns, so s3 cannot be used afterwards.",
+    },
+    Snippet {
+        id: "two_mutable_borrows",
+        title: "Two mutable references at the same time",
+        code: include_str!("../gallery/two_mutable_borrows.rs"),
+        expected: "E0499",
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        explanation: "At any given time you can have one mutable reference to a value.
+r1 is still used by the println!, so r2 would be a second mutable reference while the first is alive.",
+    },
+    Snippet {
+        id: "dangle",
+        title: "Returning a reference to a local String",
+        code: include_str!("../gallery/dangle.rs"),
+        expected: "E0106",
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        explanation: "s is dropped when dangle() returns, so a reference to it would point to freed memory.
+The function has no parameter the reference could borrow from, so the compiler asks for a lifetime.
+Returning the String itself moves ownership out and nothing is deallocated.",
+    },
+];
+
+// What rustc printed for a snippet, and the error codes found in it
+pub struct Diagnostic {
+    pub codes: Vec<String>,
+    pub message: String,
+}
+
+// Looks a snippet up by its id, or by an error code it demonstrates (the first snippet wins)
+pub fn find(name: &str) -> Option<&'static Snippet> {
+    SNIPPETS
+        .iter()
+        .find(|snippet| snippet.id == name || snippet.expected.eq_ignore_ascii_case(name))
+}
+
+// Copies the snippet into a scratch directory and type-checks it as a library, warnings switched off
+pub fn compile(snippet: &Snippet) -> io::Result<Diagnostic> {
+    let dir = env::temp_dir().join(format!(
+        "hello_cargo-gallery-{}-{}",
+        process::id(),
+        snippet.id
+    ));
+    fs::create_dir_all(&dir)?;
+    let file = format!("{}.rs", snippet.id);
+    fs::write(dir.join(&file), snippet.code)?;
+
+    let output = rustc()
+        .current_dir(&dir)
+        .args([
+            "--edition",
+            "2024",
+            "--crate-type",
+            "lib",
+            "--emit",
+            "metadata",
+        ])
+        .args(["--cap-lints", "allow", "--color", "never"])
+        .args(["--out-dir", ".", &file])
+        .output();
+    // The scratch files are of no use once rustc has run, whatever it reported
+    let _ = fs::remove_dir_all(&dir);
+    let output = output?;
+
+    let message = String::from_utf8_lossy(&output.stderr).into_owned();
+    Ok(Diagnostic {
+        codes: error_codes(&message),
+        message,
+    })
+}
+
+// The local compiler, as described at the top of this file
+pub fn rustc() -> Command {
+    Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+// `error[E0499]: cannot borrow ...` -> E0499
+fn error_codes(message: &str) -> Vec<String> {
+    message
+        .lines()
+        .filter_map(|line| line.strip_prefix("error["))
+        .filter_map(|rest| rest.split_once(']'))
+        .map(|(code, _)| code.to_string())
+        .collect()
+}
+
+// Prints the snippet, what rustc said about it and why; false when rustc did not report the expected code
+pub fn show(out: &mut Console, snippet: &Snippet) -> io::Result<bool> {
+    writeln!(
+        out,
+        "--------- {} [{}] ---------",
+        snippet.title, snippet.expected
+    )?;
+    writeln!(out, "{}", snippet.code.trim_end())?;
+    writeln!(out)?;
+
+    let diagnostic = compile(snippet)
+        .map_err(|err| io::Error::new(err.kind(), format!("could not run rustc: {err}")))?;
+    writeln!(out, "rustc says:")?;
+    writeln!(out, "{}", diagnostic.message.trim_end())?;
+    writeln!(out)?;
+
+    let matched = diagnostic.codes.iter().any(|code| code == snippet.expected);
+    if matched {
+        writeln!(out, "Expected {}, rustc agrees.", snippet.expected)?;
+    } else if diagnostic.codes.is_empty() {
+        writeln!(
+            out,
+            "Expected {}, but rustc reported no error code.",
+            snippet.expected
+        )?;
+    } else {
+        writeln!(
+            out,
+            "Expected {}, but rustc reported {}.",
+            snippet.expected,
+            diagnostic.codes.join(", ")
+        )?;
+    }
+    writeln!(out, "Why: {}", snippet.explanation)?;
+    writeln!(out, "See lesson {}", snippet.lesson)?;
+    writeln!(
+        out,
+        "More about the error: hello_cargo explain {}",
+        snippet.expected
+    )?;
+    Ok(matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons;
+
+    #[test]
+    fn error_codes_are_read_from_the_diagnostic() {
+        let message = "error[E0499]: cannot borrow `s` as mutable more than once at a time
+ --> two_mutable_borrows.rs:6:14
+error: aborting due to 1 previous error";
+        assert_eq!(error_codes(message), ["E0499"]);
+    }
+
+    #[test]
+    fn every_snippet_fails_with_its_error_code() {
+        for snippet in SNIPPETS {
+            assert!(lessons::find(snippet.lesson).is_some(), "{}", snippet.id);
+            let diagnostic = compile(snippet).unwrap();
+            assert!(
+                diagnostic.codes.iter().any(|code| code == snippet.expected),
+                "{} should fail with {}, rustc said:\n{}",
+                snippet.id,
+                snippet.expected,
+                diagnostic.message
+            );
+        }
+    }
+}
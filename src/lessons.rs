@@ -0,0 +1,260 @@
+// Lesson registry
+/*
+Every chapter of the handbook exposes a `pub fn(&mut Console)` entry point that writes its output to the console.
+Instead of commenting calls in and out of main(), each entry point is registered here
+under an id made of its module path, e.g. `basic::variables` or `ownership::slice_type`.
+The first segment of an id is the chapter the lesson belongs to.
+
+Each lesson also carries the section number used in the README (1.1 Variables, 2.1 What is ownership?),
+a title, a one line summary and the ids of the lessons it builds on.
+*/
+
+use std::io;
+
+use crate::basic::calculator::calculator;
+use crate::basic::control_flow::trace::trace;
+use crate::basic::control_flow::{control_flow, loops};
+use crate::basic::datatypes::chars::chars;
+use crate::basic::datatypes::data_types;
+use crate::basic::datatypes::floats::floats;
+use crate::basic::datatypes::integers::integers;
+use crate::basic::datatypes::layout::layout;
+use crate::basic::datatypes::overflow::overflow;
+use crate::basic::functions::functions;
+use crate::basic::variables::variable;
+use crate::console::Console;
+use crate::ownership::intro_to_ownership::borrows::borrows;
+use crate::ownership::intro_to_ownership::memory::memory;
+use crate::ownership::intro_to_ownership::{intro_to_ownership, mutable_reference};
+use crate::ownership::slice_type::slice_type;
+use crate::ownership::slice_type::unicode::unicode;
+use crate::projects::guessing_game::guessing_game;
+
+pub struct Chapter {
+    pub id: &'static str,
+    pub number: u32,
+    pub title: &'static str,
+}
+
+pub struct Lesson {
+    pub id: &'static str,
+    pub number: &'static str,
+    pub title: &'static str,
+    pub summary: &'static str,
+    pub prerequisites: &'static [&'static str],
+    pub run: fn(&mut Console) -> io::Result<()>,
+}
+
+impl Lesson {
+    pub fn chapter(&self) -> &'static str {
+        self.id.split("::").next().unwrap_or(self.id)
+    }
+}
+
+pub const CHAPTERS: &[Chapter] = &[
+    Chapter {
+        id: "basic",
+        number: 1,
+        title: "Rust Intro",
+    },
+    Chapter {
+        id: "ownership",
+        number: 2,
+        title: "Ownership",
+    },
+    Chapter {
+        id: "projects",
+        number: 3,
+        title: "Projects",
+    },
+];
+
+pub const LESSONS: &[Lesson] = &[
+    Lesson {
+        id: "basic::variables",
+        number: "1.1",
+        title: "Variables",
+        summary: "Immutable and mutable bindings, constants and shadowing",
+        prerequisites: &[],
+        run: variable,
+    },
+    Lesson {
+        id: "basic::datatypes",
+        number: "1.2",
+        title: "Datatypes",
+        summary: "Scalar types (integers, floats, booleans, chars) and the compound tuple and array types",
+        prerequisites: &["basic::variables"],
+        run: data_types,
+    },
+    Lesson {
+        id: "basic::datatypes::integers",
+        number: "1.2.1",
+        title: "Integer Types",
+        summary: "Bit width, size, alignment and range of every integer type, and which types hold a number",
+        prerequisites: &["basic::datatypes"],
+        run: integers,
+    },
+    Lesson {
+        id: "basic::datatypes::overflow",
+        number: "1.2.2",
+        title: "Integer Overflow",
+        summary: "Wrapping, checked, saturating and overflowing arithmetic, debug panics and release wrapping",
+        prerequisites: &["basic::datatypes::integers"],
+        run: overflow,
+    },
+    Lesson {
+        id: "basic::datatypes::floats",
+        number: "1.2.3",
+        title: "Floating-Point Numbers",
+        summary: "IEEE-754 sign, exponent and mantissa bits, special values and rounding errors",
+        prerequisites: &["basic::datatypes"],
+        run: floats,
+    },
+    Lesson {
+        id: "basic::datatypes::chars",
+        number: "1.2.4",
+        title: "Characters",
+        summary: "Code points, UTF-8 and UTF-16 encodings, general categories, char versus String",
+        prerequisites: &["basic::datatypes"],
+        run: chars,
+    },
+    Lesson {
+        id: "basic::datatypes::layout",
+        number: "1.2.5",
+        title: "Memory Layout",
+        summary: "Size, alignment, field offsets and padding of tuples and arrays",
+        prerequisites: &["basic::datatypes"],
+        run: layout,
+    },
+    Lesson {
+        id: "basic::control_flow",
+        number: "1.3",
+        title: "Control Flow",
+        summary: "Branching with if expressions, if in a let statement and reading a number safely",
+        prerequisites: &["basic::datatypes"],
+        run: control_flow,
+    },
+    Lesson {
+        id: "basic::control_flow::loops",
+        number: "1.4",
+        title: "Loops",
+        summary: "loop, while and for, returning values from loops and loop labels",
+        prerequisites: &["basic::control_flow"],
+        run: loops,
+    },
+    Lesson {
+        id: "basic::control_flow::trace",
+        number: "1.4.1",
+        title: "Loop Tracer",
+        summary: "An indented trace of every loop iteration and of which break or continue fired on which label",
+        prerequisites: &["basic::control_flow::loops"],
+        run: trace,
+    },
+    Lesson {
+        id: "basic::functions",
+        number: "1.5",
+        title: "Functions",
+        summary: "Parameters, statements versus expressions and return values",
+        prerequisites: &["basic::variables"],
+        run: functions,
+    },
+    Lesson {
+        id: "basic::calculator",
+        number: "1.6",
+        title: "Calculator",
+        summary: "Reading numbers from input, handling parse errors, overflow and division by zero",
+        prerequisites: &["basic::datatypes", "basic::control_flow::loops"],
+        run: calculator,
+    },
+    Lesson {
+        id: "ownership::intro_to_ownership",
+        number: "2.1",
+        title: "What is ownership?",
+        summary: "Stack and heap, the ownership rules, moves, clones and ownership across functions, traced on a timeline",
+        prerequisites: &["basic::functions"],
+        run: intro_to_ownership,
+    },
+    Lesson {
+        id: "ownership::intro_to_ownership::memory",
+        number: "2.1.1",
+        title: "Strings in Memory",
+        summary: "The real ptr, len and capacity of Strings through a move, a clone, a reallocation and a drop",
+        prerequisites: &["ownership::intro_to_ownership"],
+        run: memory,
+    },
+    Lesson {
+        id: "ownership::intro_to_ownership::mutable_reference",
+        number: "2.2",
+        title: "References and Borrowing",
+        summary: "Mutable references and the rules of references",
+        prerequisites: &["ownership::intro_to_ownership"],
+        run: mutable_reference,
+    },
+    Lesson {
+        id: "ownership::intro_to_ownership::borrows",
+        number: "2.2.1",
+        title: "Borrow Simulator",
+        summary: "Script borrows and see the conflicts the borrow checker reports, with non-lexical lifetimes",
+        prerequisites: &["ownership::intro_to_ownership::mutable_reference"],
+        run: borrows,
+    },
+    Lesson {
+        id: "ownership::slice_type",
+        number: "2.3",
+        title: "The Slice Type",
+        summary: "String slices as references into part of a String, &str parameters and slicing text into words",
+        prerequisites: &["ownership::intro_to_ownership::mutable_reference"],
+        run: slice_type,
+    },
+    Lesson {
+        id: "ownership::slice_type::unicode",
+        number: "2.3.1",
+        title: "Slicing Unicode Text",
+        summary: "Char boundaries, why &s[0..i] can panic on multi-byte text, and Unicode whitespace",
+        prerequisites: &["ownership::slice_type"],
+        run: unicode,
+    },
+    Lesson {
+        id: "projects::guessing_game",
+        number: "3.1",
+        title: "Guessing Game",
+        summary: "A complete program: random numbers, parsing input, match on Ordering and loops",
+        prerequisites: &["basic::calculator"],
+        run: guessing_game,
+    },
+];
+
+// Looks a lesson up by its full id, its section number, or by a trailing part of the id
+// as long as only one lesson matches, e.g. `variables` -> `basic::variables`
+pub fn find(name: &str) -> Option<&'static Lesson> {
+    if let Some(lesson) = LESSONS
+        .iter()
+        .find(|lesson| lesson.id == name || lesson.number == name)
+    {
+        return Some(lesson);
+    }
+    let suffix = format!("::{name}");
+    let mut matches = LESSONS.iter().filter(|lesson| lesson.id.ends_with(&suffix));
+    match (matches.next(), matches.next()) {
+        (Some(lesson), None) => Some(lesson),
+        _ => None,
+    }
+}
+
+// Looks a chapter up by id (`basic`) or number (`1`)
+pub fn find_chapter(name: &str) -> Option<&'static Chapter> {
+    CHAPTERS
+        .iter()
+        .find(|chapter| chapter.id == name || chapter.number.to_string() == name)
+}
+
+// All lessons of a chapter in handbook order, empty if there is no such chapter
+pub fn chapter(name: &str) -> Vec<&'static Lesson> {
+    let Some(chapter) = find_chapter(name) else {
+        return Vec::new();
+    };
+    LESSONS
+        .iter()
+        .filter(|lesson| lesson.chapter() == chapter.id)
+        .collect()
+}
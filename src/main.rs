@@ -1,45 +1,22 @@
+mod alloc_tracking;
 mod basic;
+mod cli;
+mod console;
+mod exercises;
+mod explain;
+mod gallery;
+mod lessons;
 mod ownership;
+mod progress;
+mod projects;
+mod quiz;
+mod shell;
+mod snapshots;
 
-use crate::basic::datatypes::data_types;
-use crate::basic::functions::functions;
-use crate::basic::variables::variable;
-use crate::basic::control_flow::control_flow;
-use crate::ownership::intro_to_ownership::intro_to_ownership;
-use crate::ownership::slice_type::slice_type;
+use std::process::ExitCode;
 
-fn main() {
-   //  println!("Hello, world!");
-   //  let mut inputA = String::new();
-   //  let mut inputB = String::new();
-   //
-   // let mut c: i8 = 0;
-   //  println!("Please input your Number 1");
-   //  io::stdin().read_line(&mut inputA).expect("Failed to read line");
-   //  println!("Please input your Number 2");
-   //  io::stdin().read_line(&mut inputB).expect("Failed to read line");
-   //
-   //    let a =   match inputA.trim().parse::<i32>() {
-   //          Ok(n) => {n},
-   //          Err(_) => {
-   //              return;
-   //          }
-   //      };
-   //  let b = match inputB.trim().parse::<i32>() {
-   //      Ok(n) => n,
-   //      Err(_) => {
-   //             return;
-   //      }
-   //  };
-   //
-   //  println!("You entered a: {0}", a);
-   //  println!("You entered b: {0}", b);
-
-    // variable();
-    // data_types();
-    // functions();
-    // control_flow();
-
-    //intro_to_ownership();
-    slice_type();
+fn main() -> ExitCode {
+    // Lessons are picked from the command line, see `hello_cargo help`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    cli::run(&args)
 }
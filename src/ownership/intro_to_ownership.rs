@@ -34,11 +34,26 @@ First, let’s take a look at the ownership rules. Keep these rules in mind as w
 
 */
 
-pub fn intro_to_ownership() {
-    basic_ownership();
+pub mod borrows;
+pub mod memory;
+
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use crate::alloc_tracking::Step;
+use crate::console::Console;
+use crate::ownership::traced::{Timeline, Traced};
+use crate::quiz::{Answer, Question};
+
+pub fn intro_to_ownership(out: &mut Console) -> io::Result<()> {
+    // Values wrapped in Traced write to the timeline when they are created, moved and dropped
+    let timeline = Timeline::new();
+    basic_ownership(out, &timeline)?;
+    // What intro_to_string() still owned was dropped when it returned
+    timeline.flush(out)
 }
 // At the start of these examples, we will look into ownership of some variables
-fn basic_ownership() {
+fn basic_ownership(out: &mut Console, timeline: &Rc<Timeline>) -> io::Result<()> {
     // Scope example
     let s = "hello";
 
@@ -46,14 +61,17 @@ fn basic_ownership() {
 
     // Let's have a block
     {
-        let s1 = "hello";
+        let _block = timeline.scope("block");
+        let s1 = Traced::new(timeline, "s1", "hello");
         // Scope lies only in this block
     }
+    timeline.flush(out)?;
     // Try to access it outside
     //println!("{:?}", s1); //Cannot find value `s1` in this scope
-    println!("{:?}", s); // Prints hello
+    // `hello_cargo gallery out_of_scope` compiles this with rustc and shows the real error
+    writeln!(out, "{:?}", s)?; // Prints hello
 
-    intro_to_string();
+    intro_to_string(out, timeline)
 }
 //println!("{:?}", s); // Cannot find value `s` in this scope
 
@@ -64,15 +82,18 @@ fn basic_ownership() {
 // But we want to look on more complex data type stored on heap not on stack, that is string.
 // String literals are convenient, but they aren’t suitable for every situation in which we may want to use text.
 // You can create a String from a string literal using the from function, like so:
-fn intro_to_string() {
+fn intro_to_string(out: &mut Console, timeline: &Rc<Timeline>) -> io::Result<()> {
+    let _scope = timeline.scope("fn intro_to_string()");
     // Create a string from variable
+    let step = Step::start();
     let s = String::from("hello");
+    step.report(out, "let s = String::from(\"hello\");")?;
     // The double colon :: operator allows us to namespace this particular from function under the String type
 
     // This kind of string can be mutated
     let mut s = String::from("hello");
     s.push_str(",world!"); // push_str() appends a literal to a String
-    println!("{}", s); // hello,world!
+    writeln!(out, "{}", s)?; // hello,world!
 
     /*
     With the String type, in order to support a mutable, growable piece of text, we need to allocate an amount of memory on the heap, unknown at compile time, to hold the contents. This means:
@@ -85,24 +106,34 @@ fn intro_to_string() {
     >. Rust takes different path: The memory is automatically, once the variable that owns it goes out of scope.
     */
 
+    let step = Step::start();
     {
-        let s = String::from("hello"); // s is valid from this point forward
+        let _block = timeline.scope("block");
+        let s = Traced::new(timeline, "s", String::from("hello")); // s is valid from this point forward
 
         // do stuff with s
     } // this scope is now over, and s is no
     // no longer valid
+    step.report(out, "{ let s = String::from(\"hello\"); }")?;
+    timeline.flush(out)?;
 
     // Rust calls a special function for us. This function is called drop, and it’s where the author of String can put the code to return the memory. Rust calls drop automatically at the closing curly bracket.
 
     let x = 5;
+    let step = Step::start();
     let y = x;
-    println!("x: {}, y: {}", x, y);
+    step.report(out, "let y = x;")?;
+    writeln!(out, "x: {}, y: {}", x, y)?;
     // Here it did not erase x when copied to y because x and y are integer values with a fixed size and pushed on stack
 
     // lets look into string version
-    let s1 = String::from("hello");
-    let s2 = s1; // Value moved here
+    let s1 = Traced::new(timeline, "s1", String::from("hello"));
+    let step = Step::start();
+    let s2 = s1.moved_to("s2"); // Value moved here
+    step.report(out, "let s2 = s1;")?;
+    timeline.flush(out)?;
     // println!("s1: {}, s2: {}", s1, s2); // Value used after being moved - compiler error
+    // `hello_cargo gallery use_after_move` shows the full message
 
     /*
     Lets look how string works:
@@ -135,75 +166,92 @@ fn intro_to_string() {
     Stack data copied but heap data remained same.
     But rust called it move.
     */
+    // ownership::intro_to_ownership::memory draws these tables with the real ptr, len and capacity
 
     // Re-assignment of value to variable
-    let mut s = String::from("hello");
-    s = String::from("hyo");
-    println!("s: {}", s);
+    let mut s = Traced::new(timeline, "s", String::from("hello"));
+    let step = Step::start();
+    s = Traced::new(timeline, "s", String::from("hyo"));
+    step.report(out, "s = String::from(\"hyo\");")?;
+    timeline.flush(out)?;
+    writeln!(out, "s: {}", *s)?;
     // Here initially declared variable s and bind it to a String with the value "hello".
     // Then, we immediately create a new String with the value "ahoy" and assign it to s.
     // So the value "hello" became redundant after assigning new value to s and was dropped off from heap.
 
     // Deep Copy (Not just stack data but also the heap data
     // We need to use the clone
-    let s3 = s.clone();
-    println!("s3: {}", s3); // s3: hyo
+    let step = Step::start();
+    let s3 = Traced::new(timeline, "s3", s.clone());
+    step.report(out, "let s3 = s.clone();")?;
+    timeline.flush(out)?;
+    writeln!(out, "s3: {}", *s3)?; // s3: hyo
     // Heap data does copied
 
     // Functions
-    println!("---------Function Ownership ------------");
-    takes_ownership(s3);
-    println!("takes_ownership() end");
-    //println!("s3: {}", s3); // Value used after being moved
-    let mut s4 = String::from("hello");
-    s4 = return_ownership(s4);
-    println!("return_ownership() end {}: {}", s4, s4); // return_ownership() end hello: hello
+    writeln!(out, "---------Function Ownership ------------")?;
+    let step = Step::start();
+    timeline.call("takes_ownership(s3)", || takes_ownership(out, s3.moved_to("some_string")))?;
+    step.report(out, "takes_ownership(s3);")?;
+    timeline.flush(out)?;
+    writeln!(out, "takes_ownership() end")?;
+    //println!("s3: {}", s3); // Value used after being moved (`hello_cargo gallery moved_into_function`)
+    let mut s4 = Traced::new(timeline, "s4", String::from("hello"));
+    let step = Step::start();
+    s4 = timeline.call("return_ownership(s4)", || return_ownership(out, s4.moved_to("some_string")))?.moved_to("s4");
+    step.report(out, "s4 = return_ownership(s4);")?;
+    timeline.flush(out)?;
+    writeln!(out, "return_ownership() end {}: {}", *s4, *s4)?; // return_ownership() end hello: hello
 
     // Tuple return
-    let (size, string) = return_ownership_tup(s4);
-    println!("return_ownership_tup() end {}: {}", string, size); // return_ownership_tup() end hello: 5
+    let (size, string) = timeline.call("return_ownership_tup(s4)", || return_ownership_tup(out, s4.moved_to("some_string")))?;
+    let string = string.moved_to("string");
+    timeline.flush(out)?;
+    writeln!(out, "return_ownership_tup() end {}: {}", *string, size)?; // return_ownership_tup() end hello: 5
 
     // But let's say I need to use same data without returning same string from function we can use the reference
 
     // This concept is called references and borrowing
-    with_reference(&string);
-    println!("string with reference end::{}", string);
+    with_reference(out, &string)?;
+    writeln!(out, "string with reference end::{}", *string)?;
     /*
     Ownership value in function with ref: hello
     string with reference end::hello
     */
-    mutable_reference();
+    // Mutable references continue in mutable_reference()
 
     // Dangling Pointer
    // let reference_to_nothing = dangle();
+    Ok(())
 }
 
 // Ownership and Functions
-fn takes_ownership(some_string: String) {
-    println!("{}", some_string);
-}
+fn takes_ownership(out: &mut Console, some_string: Traced<String>) -> io::Result<()> {
+    writeln!(out, "{}", *some_string)
+} // some_string goes out of scope and is dropped
 // If we want ownership back there where we just passed from we must need to return back the value
 
-fn return_ownership(some_string: String) -> String {
-    println!("Ownership value in function: {}", some_string);
-    some_string
+fn return_ownership(out: &mut Console, some_string: Traced<String>) -> io::Result<Traced<String>> {
+    writeln!(out, "Ownership value in function: {}", *some_string)?;
+    Ok(some_string.moved_to("return value"))
 }
 // We can also return tuples
-fn return_ownership_tup(some_string: String) -> (usize, String) {
-    println!("Ownership value in function tup: {}", some_string);
+fn return_ownership_tup(out: &mut Console, some_string: Traced<String>) -> io::Result<(usize, Traced<String>)> {
+    writeln!(out, "Ownership value in function tup: {}", *some_string)?;
     let len = some_string.len();
-    (len, some_string)
+    Ok((len, some_string.moved_to("return value")))
 }
 
 // Pass reference of string
-fn with_reference(some_string: &String) {
-    println!("Ownership value in function with ref: {}", some_string);
+fn with_reference(out: &mut Console, some_string: &String) -> io::Result<()> {
+    writeln!(out, "Ownership value in function with ref: {}", some_string)
 }
 
-fn mutable_reference() {
+pub fn mutable_reference(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "---------Mutable References ------------")?;
     let mut s = String::from("hello");
 
-    change(&mut s);
+    change(out, &mut s)?;
 
     /*
     Mutable references have one big restriction: If you have a mutable reference to a value, you can have no other references to that value.
@@ -216,6 +264,7 @@ fn mutable_reference() {
    // let r2 = &mut s; // cannot borrow `s` as mutable more than once at a time [E0499] second mutable borrow occurs here
 
     //println!("{r1}, {r2}");
+    // `hello_cargo gallery two_mutable_borrows` shows what rustc says about it
 
     let mut s = String::from("hello");
     // As always, we can use curly brackets to create a new scope, allowing for multiple mutable references, just not simultaneous ones:
@@ -224,19 +273,20 @@ fn mutable_reference() {
     } // r1 goes out of scope here, so we can make a new reference with no problems.
 
     let r2 = &mut s;
+    // ownership::intro_to_ownership::borrows lets you script borrows like these and see which ones conflict
+    Ok(())
 }
 
-fn change(some_string: &mut String) {
+fn change(out: &mut Console, some_string: &mut String) -> io::Result<()> {
     some_string.push_str(", world");
-    println!("after push {}", some_string);
+    writeln!(out, "after push {}", some_string)?;
     some_string.pop();
-    println!("after pop {}", some_string);
+    writeln!(out, "after pop {}", some_string)?;
     /*
     after push hello, world
     after pop hello, worl
     */
-
-
+    Ok(())
 }
 
 // Dangling References
@@ -252,6 +302,7 @@ fn change(some_string: &mut String) {
 //     &s
 // }
 // Error: this function's return type contains a borrowed value, but there is no value for it to be borrowed from.
+// `hello_cargo gallery dangle` shows the full E0106 message
 
 /*
 What happened above:
@@ -275,4 +326,32 @@ Note:
 
     At any given time, you can have either one mutable reference or any number of immutable references.
     References must always be valid.
-*/
\ No newline at end of file
+*/
+
+// `hello_cargo quiz ownership` asks these, see quiz.rs
+pub const QUIZ: &[Question] = &[
+    Question {
+        lesson: "ownership::intro_to_ownership",
+        function: "intro_to_string()",
+        prompt: "`let x = 5; let y = x;` Does `let y = x` move the i32?",
+        answer: Answer::Choice(
+            &["Yes, x can no longer be used", "No, the i32 is copied and x can still be used"],
+            1,
+        ),
+        explanation: "Integers have a known size and live on the stack, they are Copy, so x stays valid.",
+    },
+    Question {
+        lesson: "ownership::intro_to_ownership",
+        function: "intro_to_string()",
+        prompt: "`let s1 = String::from(\"hello\"); let s2 = s1;` Can s1 still be printed?",
+        answer: Answer::Choice(&["Yes", "No, the String moved to s2"], 1),
+        explanation: "Only the pointer, length and capacity are copied and s1 is invalidated, using it is error E0382.",
+    },
+    Question {
+        lesson: "ownership::intro_to_ownership::mutable_reference",
+        function: "change()",
+        prompt: "`change(&mut s)` is called with s = \"hello\", pushes \", world\" and then calls `pop()`. What is left in s?",
+        answer: Answer::Output("hello, worl"),
+        explanation: "pop() removes the last char, and change() works on the caller's String through the &mut reference.",
+    },
+];
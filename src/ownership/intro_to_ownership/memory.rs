@@ -0,0 +1,124 @@
+// Strings on the stack and the heap
+/*
+intro_to_string() draws the three stack fields of a String (ptr, len, capacity) with made up values.
+This lesson reads the real ones from live Strings:
+    >. s.as_ptr()     address of the first byte on the heap
+    >. s.len()        bytes in use
+    >. s.capacity()   bytes the String received from the allocator
+
+and draws them next to the heap bytes they point to, at each step of the ownership story:
+a move copies the stack fields only, clone() allocates a second heap block, push_str() past the
+capacity asks the allocator for a bigger block, and drop() gives the block back.
+
+Addresses differ on every run, only how they relate to each other matters.
+*/
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+
+// Heap bytes drawn before the diagram is cut short
+const MAX_CELLS: usize = 16;
+
+pub fn memory(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "---------Strings in memory ------------")?;
+
+    writeln!(out, "let s1 = String::from(\"hello\");")?;
+    let s1 = String::from("hello");
+    draw(out, "s1", &s1)?;
+
+    writeln!(out, "let s2 = s1; // move")?;
+    let s1_ptr = s1.as_ptr();
+    let s2 = s1;
+    draw_moved(out, "s1")?;
+    draw(out, "s2", &s2)?;
+    writeln!(
+        out,
+        "s2.ptr == old s1.ptr: {}. Only ptr, len and capacity were copied, the heap bytes stayed where they were.",
+        s2.as_ptr() == s1_ptr
+    )?;
+
+    writeln!(out, "let mut s3 = s2.clone(); // deep copy")?;
+    let mut s3 = s2.clone();
+    draw(out, "s2", &s2)?;
+    draw(out, "s3", &s3)?;
+    writeln!(
+        out,
+        "s3.ptr == s2.ptr: {}. clone() allocated a second block and copied the bytes into it.",
+        s3.as_ptr() == s2.as_ptr()
+    )?;
+
+    writeln!(
+        out,
+        "s3.push_str(\", world!\"); // needs more than the capacity"
+    )?;
+    let capacity = s3.capacity();
+    s3.push_str(", world!");
+    draw(out, "s3", &s3)?;
+    writeln!(
+        out,
+        "capacity grew from {capacity} to {}: String asked the allocator for a bigger block and the bytes were copied over unless it could grow in place.",
+        s3.capacity()
+    )?;
+
+    writeln!(out, "drop(s3);")?;
+    let s3_ptr = s3.as_ptr();
+    let s3_capacity = s3.capacity();
+    drop(s3);
+    draw_moved(out, "s3")?;
+    writeln!(
+        out,
+        "The {s3_capacity} bytes at {s3_ptr:p} went back to the allocator, s2 is untouched:"
+    )?;
+    draw(out, "s2", &s2)?;
+    writeln!(
+        out,
+        "At the closing brace s2 is dropped the same way, Rust inserts the drop call for us."
+    )?;
+    Ok(())
+}
+
+// Draws the stack fields of `s` next to the heap block they point to.
+// Bytes past len() are capacity that is reserved but not in use yet, drawn as empty cells.
+pub fn draw(out: &mut Console, name: &str, s: &String) -> io::Result<()> {
+    for line in diagram(name, s.as_ptr(), s.len(), s.capacity(), s.as_bytes()) {
+        writeln!(out, "{line}")?;
+    }
+    Ok(())
+}
+
+fn draw_moved(out: &mut Console, name: &str) -> io::Result<()> {
+    writeln!(
+        out,
+        "{name} (stack): no longer valid, using it is a compile error"
+    )
+}
+
+pub fn diagram(
+    name: &str,
+    ptr: *const u8,
+    len: usize,
+    capacity: usize,
+    bytes: &[u8],
+) -> Vec<String> {
+    let shown = capacity.min(MAX_CELLS);
+    let more = if capacity > MAX_CELLS { " ..." } else { "" };
+    let border = format!("+{}", "-----+".repeat(shown));
+    let cells: String = (0..shown)
+        .map(|index| match bytes.get(index) {
+            Some(&byte) if index < len => format!("  {}  |", byte as char),
+            _ => "     |".to_string(),
+        })
+        .collect();
+    let indexes: String = (0..shown).map(|index| format!("{index:^6}")).collect();
+    let ptr = format!("{ptr:p}");
+
+    vec![
+        format!("{:<34}heap", format!("{name} (stack)")),
+        format!("+----------+----------------+     {border}{more}"),
+        format!("| ptr      | {ptr:<14} | --> |{cells}"),
+        format!("| len      | {len:<14} |     {border}{more}"),
+        format!("| capacity | {capacity:<14} |     {}", indexes.trim_end()),
+        "+----------+----------------+".to_string(),
+    ]
+}
@@ -2,20 +2,55 @@
 /*
 Slices let you reference a contiguous sequence of elements in a collection. A slice is a kind of reference, so it does not have ownership.
 
-
+first_word() finds the first whitespace in the string and returns the slice &s[0..i] before it.
+It takes &str instead of &String, so it works on a String, a part of a String and a string literal.
+The words module adds second_word(), nth_word(), last_word() and a words() iterator on the same idea.
+ownership::slice_type::unicode shows why slicing at a byte index can panic on text beyond ASCII.
 */
-pub fn slice_type(){
+
+pub mod unicode;
+pub mod words;
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::quiz::{Answer, Question};
+use words::{first_word, last_word, nth_word, second_word, words};
+
+pub fn slice_type(out: &mut Console) -> io::Result<()> {
     let val = String::from("hello this word");
-    println!("{}", first_word(&val));
-}
-fn first_word(s: &String)  -> &str {
-    let bytes = s.as_bytes();
-    let ss:[usize;4];
-    for (i, &item) in bytes.iter().enumerate() {
-        if item == b' '{
-            return &s[0..i];
-        }
-    }
-    &s[..]
+    writeln!(out, "{}", first_word(&val))?;
 
+    // &String coerces to &str, and a slice or a literal is a &str already
+    writeln!(out, "first_word(&val[6..]): {}", first_word(&val[6..]))?;
+    writeln!(out, "first_word(\"literal text\"): {}", first_word("literal text"))?;
+
+    // Tabs, new lines and whitespace around the text separate words too
+    let text = "  Slices\tborrow\npart of a String  ";
+    writeln!(out, "text: {:?}", text)?;
+    writeln!(out, "first_word: {:?}", first_word(text))?;
+    writeln!(out, "second_word: {:?}", second_word(text))?;
+    writeln!(out, "nth_word(3): {:?}", nth_word(text, 3))?;
+    writeln!(out, "nth_word(9): {:?}", nth_word(text, 9))?;
+    writeln!(out, "last_word: {:?}", last_word(text))?;
+    writeln!(out, "words: {:?}", words(text).collect::<Vec<&str>>())?;
+    Ok(())
 }
+
+// `hello_cargo quiz ownership` asks these, see quiz.rs
+pub const QUIZ: &[Question] = &[
+    Question {
+        lesson: "ownership::slice_type",
+        function: "first_word()",
+        prompt: "What does `first_word(\"  hello this word\")` return?",
+        answer: Answer::Output("hello"),
+        explanation: "first_word() skips the whitespace in front and returns the slice up to the next whitespace.",
+    },
+    Question {
+        lesson: "ownership::slice_type::unicode",
+        function: "unicode()",
+        prompt: "`let s = String::from(\"héllo\");` What does `&s[0..2]` do?",
+        answer: Answer::Choice(&["Gives \"hé\"", "Gives \"h\"", "Panics"], 2),
+        explanation: "Slice indices count bytes, 'é' takes bytes 1..3 so byte 2 is not a char boundary.",
+    },
+];
@@ -0,0 +1,105 @@
+// Slicing Unicode text
+/*
+String slice range indices must occur at valid UTF-8 character boundaries.
+If you attempt to create a string slice in the middle of a multibyte character, your program will exit with an error.
+
+&s[0..i] counts bytes, not characters. 'h' takes one byte in UTF-8, 'é' two, '世' three,
+so in "héllo" byte 2 lies inside 'é' and &s[0..2] panics. The first first_word() was safe only because it
+looked for the byte b' ', and an ASCII byte never appears inside a multi-byte character.
+It could not see the ideographic space U+3000 or the no-break space U+00A0 though, and searching for their
+code point as a byte, like 0xA0, finds the second byte of the UTF-8 encoding and the slice panics.
+
+Safe alternatives:
+    >. s.get(0..i) checks the boundary and returns None instead of panicking.
+    >. s.is_char_boundary(i) tells whether byte i starts a character.
+    >. char_indices() gives the byte index of each character, which is always a boundary.
+    >. words::char_slice(s, 0..2) slices by character positions instead of bytes.
+*/
+
+use std::io::{self, Write};
+
+use crate::console::Console;
+use crate::ownership::slice_type::words::{char_slice, first_word};
+
+pub fn unicode(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "---------Slicing Unicode Text------------")?;
+    let s = String::from("héllo wörld");
+    writeln!(
+        out,
+        "{s:?} is {} bytes but {} chars",
+        s.len(),
+        s.chars().count()
+    )?;
+    for (index, c) in s.char_indices().take(5) {
+        writeln!(out, "  {c:?} bytes {index}..{}", index + c.len_utf8())?;
+    }
+
+    writeln!(out, "&s[0..1] = {:?}", &s[0..1])?;
+    slice_prefix(out, &s, 2)?;
+    writeln!(out, "&s[0..3] = {:?}", &s[0..3])?;
+    writeln!(out, "s.get(0..2) = {:?}", s.get(0..2))?;
+    writeln!(out, "s.get(0..3) = {:?}", s.get(0..3))?;
+    writeln!(
+        out,
+        "s.is_char_boundary(2) = {}, s.is_char_boundary(3) = {}",
+        s.is_char_boundary(2),
+        s.is_char_boundary(3)
+    )?;
+    writeln!(out, "char_slice(&s, 0..2) = {:?}", char_slice(&s, 0..2))?;
+
+    writeln!(out, "---------Whitespace beyond ASCII------------")?;
+    for text in ["こんにちは\u{3000}世界", "100\u{a0}km"] {
+        writeln!(out, "{text:?}")?;
+        writeln!(out, "  first_word_bytes: {:?}", first_word_bytes(text))?;
+        writeln!(out, "  first_word:       {:?}", first_word(text))?;
+    }
+
+    // Searching for the no-break space by the value of its code point
+    let s = "100\u{a0}km";
+    let index = s
+        .bytes()
+        .position(|byte| byte == 0xA0)
+        .expect("0xA0 is the second byte of the no-break space");
+    writeln!(
+        out,
+        "Byte 0xA0 is at index {index}, the UTF-8 encoding of U+00A0 is {:02X?}",
+        '\u{a0}'.to_string().as_bytes()
+    )?;
+    slice_prefix(out, s, index)
+}
+
+// The first first_word(): it only knows the ASCII space
+fn first_word_bytes(s: &str) -> &str {
+    let bytes = s.as_bytes();
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+    s
+}
+
+// Prints &s[0..end], or the message it would panic with instead of panicking
+fn slice_prefix(out: &mut Console, s: &str, end: usize) -> io::Result<()> {
+    match s.get(0..end) {
+        Some(prefix) => writeln!(out, "&s[0..{end}] = {prefix:?}"),
+        None => writeln!(
+            out,
+            "&s[0..{end}] panics: {}",
+            boundary_panic(s, end)
+                .unwrap_or_else(|| format!("end byte index {end} is out of range"))
+        ),
+    }
+}
+
+// The message of the panic in &s[..index], None when index is a char boundary
+fn boundary_panic(s: &str, index: usize) -> Option<String> {
+    if index > s.len() || s.is_char_boundary(index) {
+        return None;
+    }
+    let (start, c) = s.char_indices().rev().find(|(start, _)| *start < index)?;
+    Some(format!(
+        "end byte index {index} is not a char boundary; it is inside {c:?} (bytes {start}..{}) of `{s}`",
+        start + c.len_utf8()
+    ))
+}
@@ -0,0 +1,152 @@
+// Word slices
+/*
+first_word() from the slice lesson, grown into a small family of functions.
+All of them return slices into the text they are given, so no word is ever copied,
+and the borrow checker makes sure the text outlives the words taken from it.
+
+They take &str rather than &String: a &String coerces to &str, so they work on whole Strings,
+on slices of Strings and on string literals alike.
+
+Words are separated by whitespace: spaces, tabs and newlines, and any whitespace at the start
+or the end of the text is skipped, so "  hello\tworld\n" holds the two words "hello" and "world".
+Whitespace is whatever char::is_whitespace() says, which includes Unicode spaces such as the
+no-break space U+00A0 and the ideographic space U+3000 that separates words in Japanese text.
+Every slice starts and ends on a char boundary, so slicing never lands inside a multi-byte character.
+*/
+
+use std::iter;
+use std::ops::Range;
+
+// Iterator over the words of a text, made by words()
+pub struct Words<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        // char_indices() gives the byte index where each char starts, and those are always char boundaries
+        let mut chars = self.rest.char_indices();
+        let Some((start, _)) = chars.find(|(_, c)| !c.is_whitespace()) else {
+            self.rest = "";
+            return None;
+        };
+        let end = chars
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(self.rest.len(), |(end, _)| end);
+        let word = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+        Some(word)
+    }
+}
+
+pub fn words(s: &str) -> Words<'_> {
+    Words { rest: s }
+}
+
+// The first word, or "" when the text has no words
+pub fn first_word(s: &str) -> &str {
+    words(s).next().unwrap_or("")
+}
+
+// The second word, or "" when the text has less than two words
+pub fn second_word(s: &str) -> &str {
+    nth_word(s, 1).unwrap_or("")
+}
+
+// The word at position n, counting from 0 like indexes do
+pub fn nth_word(s: &str, n: usize) -> Option<&str> {
+    words(s).nth(n)
+}
+
+// The last word, or "" when the text has no words
+pub fn last_word(s: &str) -> &str {
+    words(s).last().unwrap_or("")
+}
+
+// Slices by char positions instead of byte indexes, None when the range does not fit in the text.
+// &s[0..2] counts bytes and panics when byte 2 is inside a character, char_slice(s, 0..2) is the first two chars.
+pub fn char_slice(s: &str, chars: Range<usize>) -> Option<&str> {
+    // The byte index of every char boundary, the end of the text included
+    let mut boundaries = s
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(iter::once(s.len()));
+    let start = boundaries.nth(chars.start)?;
+    let end = match chars.len() {
+        0 if chars.end < chars.start => return None,
+        0 => start,
+        len => boundaries.nth(len - 1)?,
+    };
+    Some(&s[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_are_split_on_any_whitespace() {
+        let text = "  hello\tthis \n word\r\n";
+        assert_eq!(words(text).collect::<Vec<_>>(), ["hello", "this", "word"]);
+        assert_eq!(words("").count(), 0);
+        assert_eq!(words(" \t\n ").count(), 0);
+    }
+
+    #[test]
+    fn words_are_picked_by_position() {
+        let text = String::from("hello this word");
+        assert_eq!(first_word(&text), "hello");
+        assert_eq!(second_word(&text), "this");
+        assert_eq!(nth_word(&text, 2), Some("word"));
+        assert_eq!(nth_word(&text, 3), None);
+        assert_eq!(last_word(&text), "word");
+    }
+
+    #[test]
+    fn a_single_word_is_first_and_last() {
+        assert_eq!(first_word("hello"), "hello");
+        assert_eq!(last_word("hello"), "hello");
+        assert_eq!(second_word("hello"), "");
+        assert_eq!(first_word("\thello\n"), "hello");
+    }
+
+    #[test]
+    fn text_without_words_gives_empty_slices() {
+        assert_eq!(first_word(""), "");
+        assert_eq!(first_word("   "), "");
+        assert_eq!(last_word("\n\t"), "");
+        assert_eq!(nth_word("", 0), None);
+    }
+
+    #[test]
+    fn unicode_whitespace_separates_words() {
+        assert_eq!(first_word("こんにちは\u{3000}世界"), "こんにちは");
+        assert_eq!(last_word("こんにちは\u{3000}世界"), "世界");
+        assert_eq!(
+            words("100\u{a0}km\u{2003}away").collect::<Vec<_>>(),
+            ["100", "km", "away"]
+        );
+        assert_eq!(second_word("\u{3000}héllo wörld\u{a0}"), "wörld");
+    }
+
+    #[test]
+    fn char_slices_count_chars_not_bytes() {
+        let text = "héllo wörld";
+        assert_eq!(char_slice(text, 0..2), Some("hé"));
+        assert_eq!(char_slice(text, 6..11), Some("wörld"));
+        assert_eq!(char_slice(text, 11..11), Some(""));
+        let (start, end) = (3, 2);
+        assert_eq!(char_slice(text, start..end), None);
+        assert_eq!(char_slice(text, 10..12), None);
+        assert_eq!(char_slice("世界", 1..2), Some("界"));
+    }
+
+    #[test]
+    fn words_point_into_the_original_text() {
+        let text = String::from("hello world");
+        let word = second_word(&text);
+        assert_eq!(word.as_ptr(), text[6..].as_ptr());
+    }
+}
@@ -0,0 +1,124 @@
+// Drop tracer
+/*
+Rust inserts a call to drop() wherever an owner goes out of scope, but nothing is printed when it happens.
+Traced<T> wraps a value and writes every step of its life to a Timeline:
+    >. Traced::new(&timeline, "s", value)   the value is created and bound to `s`
+    >. s.moved_to("some_string")            ownership moves to another binding, e.g. a function parameter
+    >. drop                                 the owner went out of scope, from the Drop impl
+
+Scopes are marked with Timeline::scope() and function calls with Timeline::call(), and every event is
+indented by the scope depth it happened at, so the printed timeline reads like the code.
+
+The timeline is shared through an Rc, because the Drop impl has nothing else to write to.
+Logging runs untracked (see alloc_tracking.rs), so the allocation counts of a lesson step stay those
+of the lesson's own Strings.
+*/
+
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Display};
+use std::io::{self, Write};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use crate::alloc_tracking;
+use crate::console::Console;
+
+#[derive(Default)]
+pub struct Timeline {
+    events: RefCell<Vec<String>>,
+    depth: Cell<usize>,
+}
+
+impl Timeline {
+    pub fn new() -> Rc<Timeline> {
+        Rc::new(Timeline::default())
+    }
+
+    // Takes `format_args!(...)` rather than a String, so the event is only formatted once untracked
+    pub fn log(&self, event: impl Display) {
+        alloc_tracking::untracked(|| {
+            let indent = "    ".repeat(self.depth.get());
+            self.events.borrow_mut().push(format!("{indent}{event}"));
+        });
+    }
+
+    // Logs `{`, and `}` when the returned guard goes out of scope.
+    // Declare the guard first in a block so it is dropped after everything else in the block.
+    pub fn scope(self: &Rc<Self>, label: impl Display) -> ScopeGuard {
+        self.log(format_args!("{label} {{"));
+        self.depth.set(self.depth.get() + 1);
+        ScopeGuard {
+            timeline: Rc::clone(self),
+        }
+    }
+
+    // Runs a function call one level deeper, so its parameters are dropped inside it
+    pub fn call<R>(self: &Rc<Self>, signature: &str, f: impl FnOnce() -> R) -> R {
+        let guard = self.scope(format_args!("call {signature}"));
+        let result = f();
+        drop(guard);
+        result
+    }
+
+    // Writes the events so far to the console and starts over
+    pub fn flush(&self, out: &mut Console) -> io::Result<()> {
+        let events = alloc_tracking::untracked(|| self.events.take());
+        for event in &events {
+            writeln!(out, "  timeline | {event}")?;
+        }
+        alloc_tracking::untracked(|| drop(events));
+        Ok(())
+    }
+}
+
+pub struct ScopeGuard {
+    timeline: Rc<Timeline>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let depth = self.timeline.depth.get();
+        self.timeline.depth.set(depth.saturating_sub(1));
+        self.timeline.log("}");
+    }
+}
+
+pub struct Traced<T: Debug> {
+    name: &'static str,
+    value: T,
+    timeline: Rc<Timeline>,
+}
+
+impl<T: Debug> Traced<T> {
+    pub fn new(timeline: &Rc<Timeline>, name: &'static str, value: T) -> Traced<T> {
+        timeline.log(format_args!("create {name} = {value:?}"));
+        Traced {
+            name,
+            value,
+            timeline: Rc::clone(timeline),
+        }
+    }
+
+    // Moving a Traced does not run any code, so the move is logged by hand where it happens
+    pub fn moved_to(mut self, name: &'static str) -> Traced<T> {
+        self.timeline
+            .log(format_args!("move {} -> {name}", self.name));
+        self.name = name;
+        self
+    }
+}
+
+impl<T: Debug> Deref for Traced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Debug> Drop for Traced<T> {
+    fn drop(&mut self) {
+        self.timeline
+            .log(format_args!("drop {} ({:?})", self.name, self.value));
+    }
+}
@@ -0,0 +1,254 @@
+// Learner progress
+/*
+Remembers what a learner has done between runs, in a small JSON file written with serde_json:
+
+    {
+      "lessons": [
+        "basic::datatypes",
+        "basic::variables"
+      ],
+      "quizzes": [
+        "basic"
+      ],
+      "exercises": [
+        "first_word"
+      ]
+    }
+
+    lessons     lessons that ran to the end, by `hello_cargo run` or from the shell
+    quizzes     chapters whose quiz was passed, see quiz.rs
+    exercises   exercises whose hidden tests all passed, see exercises.rs
+
+The file is progress.json in the hello_cargo directory of dirs::data_dir()
+($XDG_DATA_HOME or ~/.local/share on Linux, ~/Library/Application Support on macOS, %APPDATA% on Windows),
+or the file named by the HELLO_CARGO_PROGRESS environment variable.
+Keys other than lessons, quizzes and exercises are ignored, so a file written by a newer version still loads.
+Without either, progress is not recorded and `hello_cargo progress` asks for one of them.
+
+`hello_cargo progress` draws a completion bar for each chapter of the lesson registry:
+every lesson, the quiz when the chapter has questions, and every exercise of the chapter count as one step.
+*/
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::console::Console;
+use crate::exercises::EXERCISES;
+use crate::lessons::{self, CHAPTERS};
+use crate::quiz;
+
+const BAR_WIDTH: usize = 20;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    pub lessons: BTreeSet<String>,
+    pub quizzes: BTreeSet<String>,
+    pub exercises: BTreeSet<String>,
+}
+
+pub const NO_PATH: &str =
+    "no data directory found, set HOME or HELLO_CARGO_PROGRESS to keep track of progress";
+
+// None when there is no data directory, rather than writing to the current directory
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("HELLO_CARGO_PROGRESS") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::data_dir().map(|dir| dir.join("hello_cargo").join("progress.json"))
+}
+
+impl Progress {
+    // A missing file is a learner who has not started yet
+    pub fn load(path: &Path) -> io::Result<Progress> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(parse(&text)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Progress::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        // Written next to the file and renamed, so an interrupted save never leaves half a file
+        let temporary = path.with_extension("json.tmp");
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        fs::write(&temporary, json)?;
+        fs::rename(&temporary, path)
+    }
+}
+
+fn parse(text: &str) -> serde_json::Result<Progress> {
+    serde_json::from_str(text)
+}
+
+// Loads the progress file, applies the change and saves it again.
+// Progress is a convenience, so a file that cannot be read or written is reported on stderr
+// and the lesson, quiz or exercise goes on as if nothing happened.
+pub fn record(change: impl FnOnce(&mut Progress)) {
+    let Some(path) = path() else {
+        eprintln!("warning: progress is not recorded, {NO_PATH}");
+        return;
+    };
+    let result = Progress::load(&path).and_then(|mut progress| {
+        change(&mut progress);
+        progress.save(&path)
+    });
+    if let Err(err) = result {
+        eprintln!(
+            "warning: could not record progress in {}: {err}",
+            path.display()
+        );
+    }
+}
+
+pub fn lesson_run(id: &str) {
+    record(|progress| {
+        progress.lessons.insert(id.to_string());
+    });
+}
+
+pub fn quiz_passed(chapter: &str) {
+    record(|progress| {
+        progress.quizzes.insert(chapter.to_string());
+    });
+}
+
+pub fn exercise_solved(id: &str) {
+    record(|progress| {
+        progress.exercises.insert(id.to_string());
+    });
+}
+
+pub fn report(out: &mut Console, progress: &Progress) -> io::Result<()> {
+    for chapter in CHAPTERS {
+        let lessons = lessons::chapter(chapter.id);
+        let lessons_done = lessons
+            .iter()
+            .filter(|lesson| progress.lessons.contains(lesson.id))
+            .count();
+        let has_quiz = !quiz::questions(chapter.id).is_empty();
+        let quiz_done = has_quiz && progress.quizzes.contains(chapter.id);
+        let exercises: Vec<&str> = EXERCISES
+            .iter()
+            .filter(|exercise| {
+                lessons::find(exercise.lesson).is_some_and(|lesson| lesson.chapter() == chapter.id)
+            })
+            .map(|exercise| exercise.id)
+            .collect();
+        let exercises_done = exercises
+            .iter()
+            .filter(|id| progress.exercises.contains(**id))
+            .count();
+
+        let total = lessons.len() + usize::from(has_quiz) + exercises.len();
+        let done = lessons_done + usize::from(quiz_done) + exercises_done;
+        writeln!(
+            out,
+            "{}. {:<12} {} {:>3}%",
+            chapter.number,
+            chapter.title,
+            bar(done, total),
+            percent(done, total)
+        )?;
+
+        let mut details = vec![format!("{lessons_done} of {} lessons", lessons.len())];
+        if quiz_done {
+            details.push("quiz passed".to_string());
+        } else if has_quiz {
+            details.push("quiz not passed".to_string());
+        }
+        if !exercises.is_empty() {
+            details.push(format!("{exercises_done} of {} exercises", exercises.len()));
+        }
+        writeln!(out, "   {}", details.join(", "))?;
+        if let Some(next) = lessons
+            .iter()
+            .find(|lesson| !progress.lessons.contains(lesson.id))
+        {
+            writeln!(
+                out,
+                "   next: {} {} (hello_cargo run {})",
+                next.number, next.title, next.id
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn percent(done: usize, total: usize) -> usize {
+    // A chapter with nothing to do is complete
+    (done * 100).checked_div(total).unwrap_or(100)
+}
+
+fn bar(done: usize, total: usize) -> String {
+    let filled = percent(done, total) * BAR_WIDTH / 100;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_survives_a_save_and_load() {
+        let path = env::temp_dir()
+            .join(format!("hello_cargo-progress-{}", std::process::id()))
+            .join("progress.json");
+        assert_eq!(Progress::load(&path).unwrap(), Progress::default());
+
+        let mut progress = Progress::default();
+        progress.lessons.insert("basic::variables".to_string());
+        progress.lessons.insert("quoted \"id\"\\".to_string());
+        progress.quizzes.insert("basic".to_string());
+        progress.save(&path).unwrap();
+        let loaded = Progress::load(&path);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(loaded.unwrap(), progress);
+    }
+
+    #[test]
+    fn broken_files_are_rejected() {
+        let progress = parse("{ \"lessons\": [\"basic::variables\"], \"later\": [] }").unwrap();
+        assert!(progress.lessons.contains("basic::variables"));
+        assert!(parse("{}").unwrap().lessons.is_empty());
+        assert!(parse("{ \"lessons\": [\"basic::variables\" }").is_err());
+        assert!(parse("{ \"lessons\": [] } trailing").is_err());
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn every_json_string_escape_is_read() {
+        let progress =
+            parse(r#"{ "lessons": ["a\"\\\/b", "\b\f\n\r\t", "\u00e9\ud83e\udd80"] }"#).unwrap();
+        let lessons: Vec<&str> = progress.lessons.iter().map(String::as_str).collect();
+        assert_eq!(lessons, ["\u{8}\u{c}\n\r\t", "a\"\\/b", "é🦀"]);
+        assert!(parse(r#"{ "lessons": ["\ud83e"] }"#).is_err());
+        assert!(parse(r#"{ "lessons": ["\udd80"] }"#).is_err());
+        assert!(parse(r#"{ "lessons": ["\ud83e\u0041"] }"#).is_err());
+        assert!(parse(r#"{ "lessons": ["\u12"] }"#).is_err());
+    }
+
+    #[test]
+    fn chapters_show_a_completion_bar() {
+        let mut progress = Progress::default();
+        for lesson in lessons::chapter("projects") {
+            progress.lessons.insert(lesson.id.to_string());
+        }
+        progress.lessons.insert("basic::variables".to_string());
+        let mut console = Console::buffered();
+        report(&mut console, &progress).unwrap();
+        let output = console.into_output();
+        assert!(output.contains(&format!("3. Projects     {} 100%", bar(1, 1))));
+        assert!(output.contains("1 of 12 lessons, quiz not passed"));
+        assert!(output.contains("next: 1.2 Datatypes"));
+    }
+}
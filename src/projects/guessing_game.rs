@@ -0,0 +1,289 @@
+// Programming a Guessing Game
+/*
+The classic first project: the program picks a secret number and the player guesses until they find it,
+getting "Too small!" or "Too big!" after every guess.
+
+It pulls together everything from the basics chapter:
+    >. `rand` (from Cargo.toml) generates the secret number with random_range(1..=100).
+    >. parse::<u32>() turns the typed text into a number, and a match on the Result skips bad input.
+    >. guess.cmp(&secret) returns an Ordering: Less, Greater or Equal, and a match handles each case.
+    >. A loop keeps asking until the player wins or runs out of attempts.
+
+The random number generator comes from the console, so a scripted game always picks the same secret.
+*/
+
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::console::Console;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=50,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    pub fn attempts(self) -> usize {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Normal => 7,
+            Difficulty::Hard => 10,
+        }
+    }
+
+    pub fn parse(input: &str) -> Option<Difficulty> {
+        match input.trim().to_lowercase().as_str() {
+            "easy" | "e" | "1" => Some(Difficulty::Easy),
+            // Pressing Enter picks the default
+            "normal" | "n" | "2" | "" => Some(Difficulty::Normal),
+            "hard" | "h" | "3" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    TooSmall,
+    TooBig,
+    Correct,
+    OutOfRange,
+    // The secret was found or the attempts ran out, nothing more is compared
+    GameOver,
+}
+
+pub struct Game {
+    secret: u32,
+    difficulty: Difficulty,
+    history: Vec<u32>,
+    won: bool,
+}
+
+impl Game {
+    pub fn new(difficulty: Difficulty, rng: &mut impl Rng) -> Game {
+        Game {
+            secret: rng.random_range(difficulty.range()),
+            difficulty,
+            history: Vec::new(),
+            won: false,
+        }
+    }
+
+    // Guesses outside the range are rejected without costing an attempt
+    pub fn guess(&mut self, guess: u32) -> Verdict {
+        if self.is_over() {
+            return Verdict::GameOver;
+        }
+        if !self.difficulty.range().contains(&guess) {
+            return Verdict::OutOfRange;
+        }
+        self.history.push(guess);
+        match guess.cmp(&self.secret) {
+            Ordering::Less => Verdict::TooSmall,
+            Ordering::Greater => Verdict::TooBig,
+            Ordering::Equal => {
+                self.won = true;
+                Verdict::Correct
+            }
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.won || self.attempts_left() == 0
+    }
+
+    pub fn history(&self) -> &[u32] {
+        &self.history
+    }
+
+    pub fn attempts_left(&self) -> usize {
+        self.difficulty
+            .attempts()
+            .saturating_sub(self.history.len())
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+}
+
+pub fn guessing_game(out: &mut Console) -> io::Result<()> {
+    writeln!(out, "-----------Guessing Game-------------")?;
+    let Some(difficulty) = read_difficulty(out)? else {
+        return writeln!(out, "Maybe next time!");
+    };
+    let mut game = Game::new(difficulty, &mut out.rng());
+    let range = difficulty.range();
+    writeln!(
+        out,
+        "I'm thinking of a number between {} and {}. You have {} attempts.",
+        range.start(),
+        range.end(),
+        difficulty.attempts()
+    )?;
+
+    while game.attempts_left() > 0 {
+        writeln!(out, "Please input your guess.")?;
+        let Some(input) = out.read_line()? else {
+            return writeln!(out, "Giving up? The secret number was {}.", game.secret());
+        };
+        if input.trim() == "quit" {
+            return writeln!(out, "Giving up? The secret number was {}.", game.secret());
+        }
+        let guess: u32 = match input.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                writeln!(out, "`{}` is not a number, try again.", input.trim())?;
+                continue;
+            }
+        };
+
+        writeln!(out, "You guessed: {guess}")?;
+        match game.guess(guess) {
+            Verdict::OutOfRange => {
+                writeln!(
+                    out,
+                    "The secret number is between {} and {}.",
+                    range.start(),
+                    range.end()
+                )?;
+                continue;
+            }
+            Verdict::TooSmall => writeln!(out, "Too small!")?,
+            Verdict::TooBig => writeln!(out, "Too big!")?,
+            Verdict::Correct => {
+                return writeln!(
+                    out,
+                    "You win! It took {}: {}",
+                    plural(game.history().len(), "guess", "guesses"),
+                    format_history(game.history())
+                );
+            }
+            Verdict::GameOver => break,
+        }
+        writeln!(
+            out,
+            "Guesses so far: {} ({} left)",
+            format_history(game.history()),
+            plural(game.attempts_left(), "attempt", "attempts")
+        )?;
+    }
+    writeln!(
+        out,
+        "Out of attempts! The secret number was {}.",
+        game.secret()
+    )
+}
+
+fn read_difficulty(out: &mut Console) -> io::Result<Option<Difficulty>> {
+    loop {
+        writeln!(
+            out,
+            "Choose a difficulty: easy (1-50), normal (1-100) or hard (1-1000) [normal]"
+        )?;
+        let Some(input) = out.read_line()? else {
+            return Ok(None);
+        };
+        match Difficulty::parse(&input) {
+            Some(difficulty) => return Ok(Some(difficulty)),
+            None => writeln!(out, "`{}` is not a difficulty, try again.", input.trim())?,
+        }
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{count} {}", if count == 1 { one } else { many })
+}
+
+fn format_history(history: &[u32]) -> String {
+    history
+        .iter()
+        .map(|guess| guess.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn same_seed_picks_same_secret() {
+        let first = Game::new(Difficulty::Hard, &mut StdRng::seed_from_u64(7));
+        let second = Game::new(Difficulty::Hard, &mut StdRng::seed_from_u64(7));
+        assert_eq!(first.secret(), second.secret());
+        assert!(Difficulty::Hard.range().contains(&first.secret()));
+    }
+
+    #[test]
+    fn guesses_are_compared_and_recorded() {
+        let mut game = Game::new(Difficulty::Easy, &mut StdRng::seed_from_u64(1));
+        let secret = game.secret();
+        assert_eq!(game.guess(51), Verdict::OutOfRange);
+        assert_eq!(game.guess(0), Verdict::OutOfRange);
+        if secret > 1 {
+            assert_eq!(game.guess(secret - 1), Verdict::TooSmall);
+        }
+        if secret < 50 {
+            assert_eq!(game.guess(secret + 1), Verdict::TooBig);
+        }
+        assert_eq!(game.guess(secret), Verdict::Correct);
+        assert_eq!(game.history().last(), Some(&secret));
+        assert_eq!(
+            game.attempts_left(),
+            Difficulty::Easy.attempts() - game.history().len()
+        );
+    }
+
+    #[test]
+    fn scripted_game_can_be_won() {
+        let secret = Game::new(Difficulty::Normal, &mut Console::buffered().rng()).secret();
+        let guess = secret.to_string();
+        let mut console = Console::scripted(&["normal", "abc", "0", &guess]);
+        guessing_game(&mut console).unwrap();
+        let output = console.into_output();
+        assert!(output.contains("`abc` is not a number"));
+        assert!(output.contains("The secret number is between 1 and 100."));
+        assert!(output.contains(&format!("You win! It took 1 guess: {secret}")));
+    }
+
+    #[test]
+    fn guesses_after_the_last_attempt_are_refused() {
+        let mut game = Game::new(Difficulty::Normal, &mut StdRng::seed_from_u64(3));
+        let wrong = if game.secret() == 1 { 2 } else { 1 };
+        for _ in 0..Difficulty::Normal.attempts() {
+            assert_ne!(game.guess(wrong), Verdict::GameOver);
+        }
+        assert_eq!(game.attempts_left(), 0);
+        assert_eq!(game.guess(game.secret()), Verdict::GameOver);
+        assert_eq!(game.history().len(), Difficulty::Normal.attempts());
+        assert_eq!(game.attempts_left(), 0);
+    }
+
+    #[test]
+    fn guesses_after_a_win_are_refused() {
+        let mut game = Game::new(Difficulty::Normal, &mut StdRng::seed_from_u64(3));
+        let secret = game.secret();
+        assert!(!game.is_over());
+        assert_eq!(game.guess(secret), Verdict::Correct);
+        assert!(game.is_over());
+        assert_eq!(game.guess(secret), Verdict::GameOver);
+        assert_eq!(game.guess(secret % 100 + 1), Verdict::GameOver);
+        assert_eq!(game.history(), [secret]);
+    }
+}
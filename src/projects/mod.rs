@@ -0,0 +1 @@
+pub mod guessing_game;
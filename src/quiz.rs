@@ -0,0 +1,215 @@
+// Quizzes
+/*
+Lessons declare quiz questions next to the code they ask about, as a `pub const QUIZ: &[Question]`
+in the lesson module, and QUIZZES below collects them. `hello_cargo quiz ownership` asks the questions
+of one chapter, scores the answers and explains every wrong one with the function to go back to.
+
+There are two kinds of questions:
+
+    Answer::Output   predict what the code prints, e.g. `-5 / 3`, the answer is typed as it would be printed
+    Answer::Choice   multiple choice, answered with the letter, the number or the text of an option
+
+A chapter counts as passed when at least PASS_PERCENT of its questions are answered right.
+*/
+
+use std::io::{self, Write};
+
+use crate::basic;
+use crate::console::Console;
+use crate::lessons;
+use crate::ownership;
+
+pub const PASS_PERCENT: usize = 80;
+
+pub struct Question {
+    pub lesson: &'static str,
+    pub function: &'static str,
+    pub prompt: &'static str,
+    pub answer: Answer,
+    pub explanation: &'static str,
+}
+
+pub enum Answer {
+    Output(&'static str),
+    // The options and the index of the right one
+    Choice(&'static [&'static str], usize),
+}
+
+const QUIZZES: &[&[Question]] = &[
+    basic::variables::QUIZ,
+    basic::datatypes::QUIZ,
+    basic::control_flow::QUIZ,
+    basic::functions::QUIZ,
+    ownership::intro_to_ownership::QUIZ,
+    ownership::slice_type::QUIZ,
+];
+
+pub struct Score {
+    pub correct: usize,
+    pub total: usize,
+}
+
+impl Score {
+    pub fn passed(&self) -> bool {
+        self.correct * 100 >= self.total * PASS_PERCENT
+    }
+}
+
+// The questions of a chapter in lesson order, the chapter given by id or number
+pub fn questions(chapter: &str) -> Vec<&'static Question> {
+    let lessons = lessons::chapter(chapter);
+    lessons
+        .iter()
+        .flat_map(|lesson| {
+            QUIZZES
+                .iter()
+                .flat_map(|quiz| quiz.iter())
+                .filter(|question| question.lesson == lesson.id)
+        })
+        .collect()
+}
+
+// Asks every question of the chapter, None when the learner leaves before the end
+pub fn quiz(out: &mut Console, chapter: &str) -> io::Result<Option<Score>> {
+    let questions = questions(chapter);
+    let Some(info) = lessons::find_chapter(chapter) else {
+        writeln!(out, "There is no chapter `{chapter}`.")?;
+        return Ok(None);
+    };
+    writeln!(
+        out,
+        "---------Quiz: {}. {}---------",
+        info.number, info.title
+    )?;
+    if questions.is_empty() {
+        writeln!(out, "This chapter has no questions yet.")?;
+        return Ok(None);
+    }
+    writeln!(out, "Type `quit` to stop.")?;
+
+    let mut correct = 0;
+    for (index, question) in questions.iter().enumerate() {
+        writeln!(out)?;
+        writeln!(out, "Question {} of {}", index + 1, questions.len())?;
+        writeln!(out, "{}", question.prompt)?;
+        if let Answer::Choice(options, _) = question.answer {
+            for (option, letter) in options.iter().zip('a'..) {
+                writeln!(out, "  {letter}) {option}")?;
+            }
+        }
+        write!(out, "answer> ")?;
+        let Some(answer) = out.read_line()? else {
+            writeln!(out, "Quiz stopped.")?;
+            return Ok(None);
+        };
+        if answer.trim() == "quit" {
+            writeln!(out, "Quiz stopped.")?;
+            return Ok(None);
+        }
+
+        if is_correct(question, &answer) {
+            correct += 1;
+            writeln!(out, "Right!")?;
+        } else {
+            writeln!(out, "Not quite, the answer is {}.", right_answer(question))?;
+            writeln!(out, "  {}", question.explanation)?;
+            if let Some(lesson) = lessons::find(question.lesson) {
+                writeln!(
+                    out,
+                    "  See {} in {} {} (hello_cargo run {})",
+                    question.function, lesson.number, lesson.title, lesson.id
+                )?;
+            }
+        }
+    }
+
+    let score = Score {
+        correct,
+        total: questions.len(),
+    };
+    writeln!(out)?;
+    writeln!(
+        out,
+        "Score: {} of {}, {}",
+        score.correct,
+        score.total,
+        if score.passed() {
+            "chapter passed!"
+        } else {
+            "have another look at the lessons and try again."
+        }
+    )?;
+    Ok(Some(score))
+}
+
+fn is_correct(question: &Question, answer: &str) -> bool {
+    let answer = answer.trim();
+    match question.answer {
+        // Quotes are optional, `hello` and `"hello"` both match the printed hello
+        Answer::Output(expected) => answer.trim_matches('"') == expected,
+        Answer::Choice(options, right) => {
+            let letter = char::from(b'a' + right as u8).to_string();
+            let number = (right + 1).to_string();
+            answer.eq_ignore_ascii_case(&letter)
+                || answer == number
+                || answer.eq_ignore_ascii_case(options[right])
+        }
+    }
+}
+
+fn right_answer(question: &Question) -> String {
+    match question.answer {
+        Answer::Output(expected) => format!("`{expected}`"),
+        Answer::Choice(options, right) => {
+            format!("{}) {}", char::from(b'a' + right as u8), options[right])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons::CHAPTERS;
+
+    #[test]
+    fn every_question_belongs_to_a_lesson_and_has_a_valid_answer() {
+        for question in QUIZZES.iter().flat_map(|quiz| quiz.iter()) {
+            assert!(
+                lessons::find(question.lesson).is_some(),
+                "{}",
+                question.prompt
+            );
+            if let Answer::Choice(options, right) = question.answer {
+                assert!(right < options.len(), "{}", question.prompt);
+            }
+        }
+    }
+
+    #[test]
+    fn right_answers_pass_the_chapter() {
+        for chapter in CHAPTERS {
+            let answers: Vec<String> = questions(chapter.id)
+                .iter()
+                .map(|question| match question.answer {
+                    Answer::Output(expected) => expected.to_string(),
+                    Answer::Choice(_, right) => (right + 1).to_string(),
+                })
+                .collect();
+            if answers.is_empty() {
+                continue;
+            }
+            let answers: Vec<&str> = answers.iter().map(String::as_str).collect();
+            let mut console = Console::scripted(&answers);
+            let score = quiz(&mut console, chapter.id).unwrap().unwrap();
+            assert_eq!(score.correct, score.total, "{}", chapter.id);
+        }
+    }
+
+    #[test]
+    fn wrong_answers_are_explained() {
+        let mut console = Console::scripted(&["0"; 20]);
+        let score = quiz(&mut console, "basic").unwrap().unwrap();
+        assert!(!score.passed());
+        assert!(console.into_output().contains("See numeric_operations()"));
+    }
+}
@@ -0,0 +1,207 @@
+// Interactive handbook shell
+/*
+`hello_cargo shell` shows the chapter tree from the lesson registry and reads commands from the console,
+one per line, the same way the input examples in main() read numbers:
+
+    <n> or <section>   run the n-th lesson of the menu, or a section such as `1.4`
+    (arrow up/down)    move the `>` cursor
+    (empty line)       run the lesson under the cursor
+    next / prev        run the lesson after / before the current one
+    search <term>      find lessons by id, title or summary
+    explain <error>    explain a compiler error code or message, see explain.rs
+    quiz <chapter>     answer the quiz questions of a chapter, see quiz.rs
+    menu               show the chapter tree again
+    quit               leave the shell
+
+On a terminal the commands are read with Console::read_keys(), so an arrow key moves the cursor as soon
+as it is pressed. Piped or scripted input stays in line mode, where an arrow key arrives as its escape
+sequence (`\x1b[A`, `\x1b[B`) once Enter is pressed, and a line of arrows moves the cursor once per arrow.
+*/
+
+use std::io::{self, Write};
+
+use crate::console::{ARROW_DOWN, ARROW_UP, Console};
+use crate::explain;
+use crate::lessons::{self, CHAPTERS, LESSONS, Lesson};
+use crate::progress;
+use crate::quiz;
+
+const HELP: &str =
+    "Commands: <n> | <section> | next | prev | search <term> | explain <error> | quiz <chapter> | menu | quit
+Arrow up/down moves the cursor, an empty line runs the selected lesson.";
+
+pub fn shell(out: &mut Console) -> io::Result<()> {
+    let mut cursor = 0;
+    print_menu(out, cursor)?;
+    writeln!(out, "{HELP}")?;
+
+    loop {
+        write!(out, "handbook> ")?;
+        // End of input behaves like quit
+        let Some(line) = out.read_keys()? else {
+            return Ok(());
+        };
+        let line = line.as_str();
+        if line.contains(ARROW_UP) || line.contains(ARROW_DOWN) {
+            cursor = move_cursor(cursor, line);
+            print_menu(out, cursor)?;
+            continue;
+        }
+        let (command, argument) = match line.trim().split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line.trim(), ""),
+        };
+
+        match command {
+            "quit" | "q" | "exit" => return Ok(()),
+            "menu" | "list" => print_menu(out, cursor)?,
+            "help" | "?" => writeln!(out, "{HELP}")?,
+            "" => run_lesson(out, &LESSONS[cursor])?,
+            "next" | "n" => {
+                if cursor + 1 < LESSONS.len() {
+                    cursor += 1;
+                    run_lesson(out, &LESSONS[cursor])?;
+                } else {
+                    writeln!(out, "This is the last lesson of the handbook.")?;
+                }
+            }
+            "prev" | "p" => {
+                if cursor > 0 {
+                    cursor -= 1;
+                    run_lesson(out, &LESSONS[cursor])?;
+                } else {
+                    writeln!(out, "This is the first lesson of the handbook.")?;
+                }
+            }
+            "search" => cursor = search(out, argument)?.unwrap_or(cursor),
+            "explain" if argument.is_empty() => writeln!(out, "Usage: explain <error>")?,
+            "explain" => explain::explain(out, argument)?,
+            "quiz" if argument.is_empty() => writeln!(out, "Usage: quiz <chapter>")?,
+            "quiz" => match lessons::find_chapter(argument) {
+                Some(chapter) => {
+                    if quiz::quiz(out, chapter.id)?.is_some_and(|score| score.passed()) {
+                        progress::quiz_passed(chapter.id);
+                    }
+                }
+                None => writeln!(out, "There is no chapter `{argument}`.")?,
+            },
+            choice => match select(choice) {
+                Some(index) => {
+                    cursor = index;
+                    run_lesson(out, &LESSONS[cursor])?;
+                }
+                None => writeln!(out, "Unknown command `{choice}`. {HELP}")?,
+            },
+        }
+    }
+}
+
+fn print_menu(out: &mut Console, cursor: usize) -> io::Result<()> {
+    writeln!(out, "---------------Rust handbook---------------")?;
+    for chapter in CHAPTERS {
+        writeln!(out, "{}. {}", chapter.number, chapter.title)?;
+        for lesson in lessons::chapter(chapter.id) {
+            let index = position(lesson);
+            let marker = if index == cursor { ">" } else { " " };
+            writeln!(
+                out,
+                "{marker} [{}] {} {}",
+                index + 1,
+                lesson.number,
+                lesson.title
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// Every arrow in the line moves the cursor by one, so pressing up twice before Enter moves two lessons
+fn move_cursor(mut cursor: usize, line: &str) -> usize {
+    let mut rest = line;
+    while let Some(start) = rest.find("\x1b[") {
+        let key = &rest[start..];
+        if key.starts_with(ARROW_UP) {
+            cursor = cursor.saturating_sub(1);
+        } else if key.starts_with(ARROW_DOWN) {
+            cursor = (cursor + 1).min(LESSONS.len() - 1);
+        }
+        rest = &rest[start + 2..];
+    }
+    cursor
+}
+
+// A menu index (`4`), a section number (`1.4`) or a lesson id (`control_flow::loops`)
+fn select(choice: &str) -> Option<usize> {
+    if let Ok(index) = choice.parse::<usize>() {
+        return (1..=LESSONS.len()).contains(&index).then(|| index - 1);
+    }
+    lessons::find(choice).map(position)
+}
+
+// Prints the matching lessons and returns the position of the match when there is exactly one
+fn search(out: &mut Console, term: &str) -> io::Result<Option<usize>> {
+    if term.is_empty() {
+        writeln!(out, "Usage: search <term>")?;
+        return Ok(None);
+    }
+    let term = term.to_lowercase();
+    let matches: Vec<&Lesson> = LESSONS
+        .iter()
+        .filter(|lesson| {
+            [lesson.id, lesson.title, lesson.summary]
+                .iter()
+                .any(|text| text.to_lowercase().contains(&term))
+        })
+        .collect();
+
+    match matches.as_slice() {
+        [] => {
+            writeln!(out, "No lesson matches `{term}`.")?;
+            Ok(None)
+        }
+        [lesson] => {
+            writeln!(
+                out,
+                "Selected [{}] {} {}, press Enter to run it.",
+                position(lesson) + 1,
+                lesson.number,
+                lesson.title
+            )?;
+            Ok(Some(position(lesson)))
+        }
+        found => {
+            for lesson in found {
+                writeln!(
+                    out,
+                    "  [{}] {} {}",
+                    position(lesson) + 1,
+                    lesson.number,
+                    lesson.title
+                )?;
+            }
+            Ok(None)
+        }
+    }
+}
+
+fn run_lesson(out: &mut Console, lesson: &Lesson) -> io::Result<()> {
+    writeln!(
+        out,
+        "=============== {} {} ===============",
+        lesson.number, lesson.title
+    )?;
+    (lesson.run)(out)?;
+    progress::lesson_run(lesson.id);
+    writeln!(
+        out,
+        "=============== end of {} ===============",
+        lesson.number
+    )
+}
+
+fn position(lesson: &Lesson) -> usize {
+    LESSONS
+        .iter()
+        .position(|candidate| candidate.id == lesson.id)
+        .expect("lesson comes from the registry")
+}
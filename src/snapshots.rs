@@ -0,0 +1,274 @@
+// Golden output snapshots
+/*
+What a learner sees is part of the handbook, so the captured output of every registered lesson
+is checked in under snapshots/, one file per lesson id:
+
+    basic::variables             -> snapshots/basic/variables.txt
+    basic::control_flow::loops   -> snapshots/basic/control_flow/loops.txt
+
+Heap addresses change from run to run, so every 0x... address is masked as 0x<addr> in snapshots.
+What depends on the compiler version, like the order Rust picks for the fields of a tuple, is marked
+by the lesson with COMPILER_DEPENDENT. The rest of that line and the indented lines under it are masked.
+The allocation counts printed with the `alloc-tracking` feature are left out, so the same snapshots
+are checked with and without the feature.
+
+Lessons that print the size of isize, usize or pointers have snapshots taken on a 64-bit target,
+on other targets they are skipped rather than compared.
+
+Interactive lessons are played from a script: when snapshots/basic/calculator.input exists,
+its lines are fed to the lesson as if the learner typed them. Without one the lesson sees no input.
+
+`cargo test` fails when a lesson prints something else than its snapshot.
+After an intended change, refresh the files with either of:
+
+    cargo run -- snapshots --update-snapshots
+    UPDATE_SNAPSHOTS=1 cargo test
+*/
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::alloc_tracking;
+use crate::console::Console;
+use crate::lessons::Lesson;
+
+// Ends the part of a line that snapshots keep, see mask_compiler_dependent()
+pub const COMPILER_DEPENDENT: &str = ", as laid out by this compiler";
+
+pub enum Outcome {
+    Matched,
+    Updated,
+    Missing,
+    Skipped,
+    Changed {
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    // Every line matches, only the newline at the very end is missing from or added to the output
+    TrailingNewline {
+        missing: bool,
+    },
+    // Every line matches, but one side ends its lines with \r\n, as a checkout with core.autocrlf does
+    LineEndings,
+}
+
+// Lessons whose output depends on the pointer width
+const POINTER_WIDTH_DEPENDENT: &[&str] = &["basic::datatypes::integers"];
+
+pub fn snapshot_path(lesson: &Lesson) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots");
+    path.extend(lesson.id.split("::"));
+    path.set_extension("txt");
+    path
+}
+
+pub fn capture(lesson: &Lesson) -> io::Result<String> {
+    let script = match fs::read_to_string(snapshot_path(lesson).with_extension("input")) {
+        Ok(script) => script,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let lines: Vec<&str> = script.lines().collect();
+    let mut console = Console::scripted(&lines);
+    (lesson.run)(&mut console)?;
+    Ok(mask_addresses(&mask_compiler_dependent(
+        &without_allocation_reports(&console.into_output()),
+    )))
+}
+
+fn mask_compiler_dependent(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut masking = false;
+    for line in text.split_inclusive('\n') {
+        if let Some(start) = line.find(COMPILER_DEPENDENT) {
+            masked.push_str(&line[..start + COMPILER_DEPENDENT.len()]);
+            masked.push_str(" <masked>\n");
+            masking = true;
+        } else if !(masking && line.starts_with(' ')) {
+            masked.push_str(line);
+            masking = false;
+        }
+    }
+    masked
+}
+
+fn without_allocation_reports(text: &str) -> String {
+    text.split_inclusive('\n')
+        .filter(|line| !alloc_tracking::is_report(line.trim_end_matches('\n')))
+        .collect()
+}
+
+fn mask_addresses(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("0x") {
+        let digits = rest[start + 2..]
+            .bytes()
+            .take_while(u8::is_ascii_hexdigit)
+            .count();
+        masked.push_str(&rest[..start]);
+        if digits >= 4 {
+            masked.push_str("0x<addr>");
+        } else {
+            masked.push_str(&rest[start..start + 2 + digits]);
+        }
+        rest = &rest[start + 2 + digits..];
+    }
+    masked.push_str(rest);
+    masked
+}
+
+// Compares the lesson output with its snapshot, or rewrites the snapshot when `update` is set
+pub fn check(lesson: &Lesson, update: bool) -> io::Result<Outcome> {
+    if !cfg!(target_pointer_width = "64") && POINTER_WIDTH_DEPENDENT.contains(&lesson.id) {
+        return Ok(Outcome::Skipped);
+    }
+    let actual = capture(lesson)?;
+    let path = snapshot_path(lesson);
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => Some(expected),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+
+    if expected.as_deref() == Some(actual.as_str()) {
+        return Ok(Outcome::Matched);
+    }
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, actual)?;
+        return Ok(Outcome::Updated);
+    }
+    let Some(expected) = expected else {
+        return Ok(Outcome::Missing);
+    };
+    Ok(compare(&expected, &actual))
+}
+
+// Report the first line that differs, the rest usually follows from it
+fn compare(expected: &str, actual: &str) -> Outcome {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (None, None) if actual.ends_with('\n') != expected.ends_with('\n') => {
+                return Outcome::TrailingNewline {
+                    missing: !actual.ends_with('\n'),
+                };
+            }
+            (None, None) => return Outcome::LineEndings,
+            (e, a) => {
+                return Outcome::Changed {
+                    line,
+                    expected: e.unwrap_or("<end of output>").to_string(),
+                    actual: a.unwrap_or("<end of output>").to_string(),
+                };
+            }
+        }
+    }
+}
+
+pub const LINE_ENDINGS: &str =
+    "only the line endings differ, \\r\\n against \\n, is git converting the snapshots?";
+
+pub fn trailing_newline(missing: bool) -> &'static str {
+    if missing {
+        "every line matches, only the trailing newline of the snapshot is missing from the output"
+    } else {
+        "every line matches, only the output has a trailing newline the snapshot does not"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons::LESSONS;
+
+    #[test]
+    fn addresses_are_masked() {
+        assert_eq!(
+            mask_addresses("ptr 0x55571a7c4ec0 | 0x1F value"),
+            "ptr 0x<addr> | 0x1F value"
+        );
+    }
+
+    #[test]
+    fn compiler_dependent_lines_are_masked() {
+        let text = format!(
+            "before\n(u8, u32){COMPILER_DEPENDENT}: size 8\n   0..4  .1\n  bytes: [1 1 1 1 0 _ _ _]\nafter\n  indented\n"
+        );
+        assert_eq!(
+            mask_compiler_dependent(&text),
+            format!("before\n(u8, u32){COMPILER_DEPENDENT} <masked>\nafter\n  indented\n")
+        );
+    }
+
+    #[test]
+    fn trailing_newlines_are_reported_on_their_own() {
+        assert!(matches!(
+            compare("a\nb\n", "a\nb"),
+            Outcome::TrailingNewline { missing: true }
+        ));
+        assert!(matches!(
+            compare("a\nb", "a\nb\n"),
+            Outcome::TrailingNewline { missing: false }
+        ));
+        assert!(matches!(
+            compare("a\r\nb\r\n", "a\nb\n"),
+            Outcome::LineEndings
+        ));
+        assert!(matches!(
+            compare("a\nb\n", "a\nc\n"),
+            Outcome::Changed { line: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn allocation_reports_are_left_out() {
+        assert_eq!(
+            without_allocation_reports(
+                "hello\n    let s = String::from(\"hello\");      [1 alloc, 0 frees]\nworld [1 alloc]\n"
+            ),
+            "hello\nworld [1 alloc]\n"
+        );
+    }
+
+    #[test]
+    fn every_lesson_matches_its_snapshot() {
+        let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+        let mut failures = Vec::new();
+        for lesson in LESSONS {
+            match check(lesson, update).unwrap() {
+                Outcome::Matched | Outcome::Updated | Outcome::Skipped => {}
+                Outcome::Missing => failures.push(format!(
+                    "{}: no snapshot at {}",
+                    lesson.id,
+                    snapshot_path(lesson).display()
+                )),
+                Outcome::Changed {
+                    line,
+                    expected,
+                    actual,
+                } => failures.push(format!(
+                    "{}: line {line} changed\n  - {expected}\n  + {actual}",
+                    lesson.id
+                )),
+                Outcome::TrailingNewline { missing } => {
+                    failures.push(format!("{}: {}", lesson.id, trailing_newline(missing)))
+                }
+                Outcome::LineEndings => failures.push(format!("{}: {}", lesson.id, LINE_ENDINGS)),
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "lesson output differs from the snapshots, run `cargo run -- snapshots --update-snapshots` if this is intended:\n{}",
+            failures.join("\n")
+        );
+    }
+}